@@ -0,0 +1,440 @@
+use crate::network::{Header, Stat};
+
+/// Segment of a JSON path
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// JSON path expression with an optional comparison, e.g. `$.data[0].ok == true`.
+/// Without a comparison the path only has to exist. Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::assertion::JsonPath;
+/// use std::str::FromStr;
+///
+/// let path = JsonPath::from_str("$.data[0].ok == true").unwrap();
+/// let json = serde_json::json!({ "data": [{ "ok": true }] });
+///
+/// assert!(path.evaluate(&json).is_ok());
+/// ```
+///
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    expression: String,
+    segments: Vec<Segment>,
+    comparison: Option<(bool, serde_json::Value)>,
+}
+
+#[cfg(feature = "serde")]
+impl JsonPath {
+    /// Parse the path segments after the leading `$`
+    fn parse_segments(path: &str) -> anyhow::Result<Vec<Segment>> {
+        let mut segments = vec![];
+        let mut rest = path
+            .strip_prefix('$')
+            .ok_or_else(|| anyhow::anyhow!("JSON path must start with $"))?;
+
+        while !rest.is_empty() {
+            if let Some(tail) = rest.strip_prefix('.') {
+                let end = tail.find(['.', '[']).unwrap_or(tail.len());
+                if end == 0 {
+                    anyhow::bail!("Empty key in JSON path {path}");
+                }
+                segments.push(Segment::Key(tail[..end].to_string()));
+                rest = &tail[end..];
+            } else if let Some(tail) = rest.strip_prefix('[') {
+                let end = tail
+                    .find(']')
+                    .ok_or_else(|| anyhow::anyhow!("Unclosed bracket in JSON path {path}"))?;
+                let inner = tail[..end].trim();
+                let quoted = inner
+                    .strip_prefix('"')
+                    .and_then(|key| key.strip_suffix('"'))
+                    .or_else(|| inner.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')));
+
+                let segment = match quoted {
+                    Some(key) => Segment::Key(key.to_string()),
+                    None => Segment::Index(inner.parse().map_err(|_| {
+                        anyhow::anyhow!("Invalid index {inner} in JSON path {path}")
+                    })?),
+                };
+                segments.push(segment);
+                rest = &tail[end + 1..];
+            } else {
+                anyhow::bail!("Invalid JSON path {path}, please use $.key[0].key");
+            }
+        }
+
+        Ok(segments)
+    }
+
+    /// Split the expression at the end of the path, an operator inside a bracketed key or
+    /// inside the expected value is not taken as the comparison
+    fn split_path(expression: &str) -> (&str, &str) {
+        let mut bracket = false;
+        let mut quote = None;
+        for (index, c) in expression.char_indices() {
+            match (quote, c) {
+                (Some(open), c) if c == open => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"') if bracket => quote = Some(c),
+                (None, '[') => bracket = true,
+                (None, ']') => bracket = false,
+                (None, '=' | '!') if !bracket => return expression.split_at(index),
+                (None, c) if c.is_whitespace() && !bracket => return expression.split_at(index),
+                _ => {}
+            }
+        }
+        (expression, "")
+    }
+
+    /// Select the value at the path
+    fn select<'v>(&self, json: &'v serde_json::Value) -> Option<&'v serde_json::Value> {
+        self.segments
+            .iter()
+            .try_fold(json, |value, segment| match segment {
+                Segment::Key(key) => value.get(key),
+                Segment::Index(index) => value.get(index),
+            })
+    }
+
+    /// Evaluate the expression against the JSON value, the error contains the actual value
+    pub fn evaluate(&self, json: &serde_json::Value) -> Result<(), String> {
+        let value = self.select(json).ok_or("path not found")?;
+
+        match &self.comparison {
+            None => Ok(()),
+            Some((equal, expected)) if (value == expected) == *equal => Ok(()),
+            Some(_) => Err(value.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::str::FromStr for JsonPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, rest) = Self::split_path(s.trim());
+        let rest = rest.trim();
+        let comparison = match (rest.strip_prefix("=="), rest.strip_prefix("!=")) {
+            _ if rest.is_empty() => None,
+            (Some(expected), _) => Some((true, expected)),
+            (_, Some(expected)) => Some((false, expected)),
+            _ => anyhow::bail!("Invalid comparison {rest} in JSON path {s}, please use == or !="),
+        };
+
+        let comparison = comparison.map(|(equal, expected)| {
+            let expected = expected.trim();
+            let value = serde_json::from_str(expected)
+                .unwrap_or_else(|_| serde_json::Value::String(expected.to_string()));
+            (equal, value)
+        });
+
+        Ok(Self {
+            expression: s.trim().to_string(),
+            segments: Self::parse_segments(path.trim())?,
+            comparison,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl core::fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+/// Enum for the checks on the response
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::assertion::Assertion;
+/// use cetar::network::{Header, Stat};
+/// use std::str::FromStr;
+///
+/// let stat = Stat {
+///     response_status_code: Some(500),
+///     response_headers: vec![Header::from_str("Content-Type: application/json").unwrap()],
+///     ..Default::default()
+/// };
+///
+/// let status = Assertion::Status(200);
+/// let header = Assertion::Header(Header::from_str("content-type: application/json").unwrap());
+///
+/// assert_eq!(status.check(&stat), Err("500".to_string()));
+/// assert!(header.check(&stat).is_ok());
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Assertion {
+    /// The response status code equals the value
+    Status(i32),
+    /// The response has the header, the value must be contained in the header value
+    /// ignoring case, an empty value only checks for the presence of the header
+    Header(Header),
+    /// The response body contains the text
+    BodyContains(String),
+    /// The response body is JSON matching the path expression, requires the `serde` feature
+    #[cfg(feature = "serde")]
+    JsonPath(JsonPath),
+}
+
+impl Assertion {
+    /// Check the assertion against the `Stat`, the error contains the actual value
+    pub fn check(&self, stat: &Stat) -> Result<(), String> {
+        match self {
+            Assertion::Status(expected) => match stat.response_status_code {
+                Some(code) if code == *expected => Ok(()),
+                Some(code) => Err(code.to_string()),
+                None => Err("no status".to_string()),
+            },
+            Assertion::Header(expected) => {
+                let values = stat
+                    .response_headers
+                    .iter()
+                    .filter(|header| header.key.eq_ignore_ascii_case(&expected.key))
+                    .map(|header| header.value.as_str())
+                    .collect::<Vec<_>>();
+
+                if values.is_empty() {
+                    return Err("header not found".to_string());
+                }
+
+                let wanted = expected.value.to_lowercase();
+                match values.iter().any(|v| v.to_lowercase().contains(&wanted)) {
+                    true => Ok(()),
+                    false => Err(values.join(", ")),
+                }
+            }
+            Assertion::BodyContains(text) => match stat.utf8_response_body() {
+                Some(body) if body.contains(text) => Ok(()),
+                _ => Err("text not found in body".to_string()),
+            },
+            #[cfg(feature = "serde")]
+            Assertion::JsonPath(path) => {
+                let body = stat.utf8_response_body().unwrap_or_default();
+                let json = serde_json::from_str(&body).map_err(|e| format!("invalid JSON: {e}"))?;
+                path.evaluate(&json)
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for Assertion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Assertion::Status(code) => write!(f, "status {code}"),
+            Assertion::Header(header) => write!(f, "header {header}"),
+            Assertion::BodyContains(text) => write!(f, "body contains {text:?}"),
+            #[cfg(feature = "serde")]
+            Assertion::JsonPath(path) => write!(f, "json {path}"),
+        }
+    }
+}
+
+/// Failure struct to store a failed assertion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    /// Index of the request the failure happened in, starting at 0
+    pub run: usize,
+    /// Failed assertion
+    pub assertion: Assertion,
+    /// Actual value found in the response
+    pub actual: String,
+}
+
+/// Check the assertions against every `Stat` and return the failures.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::assertion::{check, Assertion};
+/// use cetar::network::Stat;
+///
+/// let assertions = vec![Assertion::Status(200)];
+/// let stats = vec![Stat {
+///     response_status_code: Some(503),
+///     ..Default::default()
+/// }];
+///
+/// let failures = check(&assertions, &stats);
+///
+/// assert_eq!(failures.len(), 1);
+/// assert_eq!(failures[0].actual, "503");
+/// ```
+///
+pub fn check(assertions: &[Assertion], stats: &[Stat]) -> Vec<Failure> {
+    stats
+        .iter()
+        .enumerate()
+        .flat_map(|(run, stat)| {
+            assertions.iter().filter_map(move |assertion| {
+                assertion.check(stat).err().map(|actual| Failure {
+                    run,
+                    assertion: assertion.clone(),
+                    actual,
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn stat(body: &str) -> Stat {
+        Stat {
+            response_status_code: Some(200),
+            response_headers: vec![
+                Header::from_str("Content-Type: application/json; charset=utf-8").unwrap(),
+                Header::from_str("X-Request-Id: abc").unwrap(),
+            ],
+            response_body: body.as_bytes().to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_status() {
+        let stat = stat("");
+
+        assert!(Assertion::Status(200).check(&stat).is_ok());
+        assert_eq!(Assertion::Status(201).check(&stat), Err("200".to_string()));
+        assert!(Assertion::Status(200).check(&Stat::default()).is_err());
+    }
+
+    #[test]
+    fn test_header() {
+        let stat = stat("");
+        let table = vec![
+            ("content-type: application/json", true),
+            ("Content-Type: APPLICATION/JSON", true),
+            ("x-request-id:", true),
+            ("content-type: text/html", false),
+            ("x-missing: value", false),
+        ];
+
+        for (header, expected) in table {
+            let assertion = Assertion::Header(Header::from_str(header).unwrap());
+            assert_eq!(assertion.check(&stat).is_ok(), expected, "{header}");
+        }
+    }
+
+    #[test]
+    fn test_body_contains() {
+        let stat = stat("{\"ok\": true}");
+
+        assert!(Assertion::BodyContains("\"ok\"".to_string())
+            .check(&stat)
+            .is_ok());
+        assert!(Assertion::BodyContains("error".to_string())
+            .check(&stat)
+            .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_path() {
+        let stat = stat(r#"{"ok": true, "data": [{"id": 7, "name": "cetar"}], "a.b": null}"#);
+        let table = vec![
+            ("$.ok == true", true),
+            ("$.ok != true", false),
+            ("$.ok", true),
+            ("$.data[0].id == 7", true),
+            ("$.data[0].id == 8", false),
+            ("$.data[0].name == cetar", true),
+            ("$.data[0].name == \"cetar\"", true),
+            ("$.data[1]", false),
+            ("$['a.b'] == null", true),
+            ("$.missing", false),
+        ];
+
+        for (expression, expected) in table {
+            let assertion = Assertion::JsonPath(JsonPath::from_str(expression).unwrap());
+            assert_eq!(assertion.check(&stat).is_ok(), expected, "{expression}");
+        }
+
+        let assertion = Assertion::JsonPath(JsonPath::from_str("$.ok").unwrap());
+        assert!(assertion.check(&super::test::stat("not json")).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_path_operator_in_value() {
+        let stat = stat(r#"{"query": "a==b", "a==b": "x!=y"}"#);
+        let table = vec![
+            ("$.query == a==b", true),
+            ("$.query != a==b", false),
+            ("$.query == \"a==b\"", true),
+            ("$['a==b'] == x!=y", true),
+            ("$[\"a==b\"]==x!=y", true),
+            ("$['a==b'] != x", true),
+        ];
+
+        for (expression, expected) in table {
+            let assertion = Assertion::JsonPath(JsonPath::from_str(expression).unwrap());
+            assert_eq!(assertion.check(&stat).is_ok(), expected, "{expression}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_path_invalid() {
+        for expression in [
+            "ok == true",
+            "$.",
+            "$[x]",
+            "$[0",
+            "$x",
+            "$.ok = true",
+            "$.ok true",
+        ] {
+            assert!(JsonPath::from_str(expression).is_err(), "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let assertions = vec![
+            Assertion::Status(200),
+            Assertion::BodyContains("healthy".to_string()),
+        ];
+        let stats = vec![stat("healthy"), stat("unhealthy"), stat("down")];
+
+        let failures = check(&assertions, &stats);
+
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].run, 2);
+        assert_eq!(failures[0].assertion, assertions[1]);
+    }
+
+    #[test]
+    fn test_display() {
+        let table = vec![
+            (Assertion::Status(200), "status 200"),
+            (
+                Assertion::Header(Header::from_str("a: b").unwrap()),
+                "header a: b",
+            ),
+            (
+                Assertion::BodyContains("ok".to_string()),
+                "body contains \"ok\"",
+            ),
+        ];
+
+        for (assertion, expected) in table {
+            assert_eq!(assertion.to_string(), expected);
+        }
+    }
+}
@@ -0,0 +1,454 @@
+use crate::network::{Config, Header, Method, Sink, Stat, TransferError};
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+
+/// Placeholder shown instead of a credential
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Enum for the HTTP authentication methods curl can use with the user credentials
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::auth::AuthMethod;
+///
+/// assert_eq!(AuthMethod::default(), AuthMethod::Basic);
+/// assert_eq!(AuthMethod::Digest.name(), "Digest");
+/// ```
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum AuthMethod {
+    /// Basic authentication, the credentials are sent base64 encoded
+    #[default]
+    Basic,
+    /// Digest authentication
+    Digest,
+    /// NTLM authentication
+    Ntlm,
+    /// Let curl pick the most secure method the server offers
+    Any,
+}
+
+impl AuthMethod {
+    /// Human readable name of the method
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuthMethod::Basic => "Basic",
+            AuthMethod::Digest => "Digest",
+            AuthMethod::Ntlm => "NTLM",
+            AuthMethod::Any => "Any",
+        }
+    }
+}
+
+impl From<AuthMethod> for curl::easy::Auth {
+    fn from(method: AuthMethod) -> Self {
+        let mut auth = curl::easy::Auth::new();
+        match method {
+            AuthMethod::Basic => auth.basic(true),
+            AuthMethod::Digest => auth.digest(true),
+            AuthMethod::Ntlm => auth.ntlm(true),
+            AuthMethod::Any => auth.auto(true),
+        };
+        auth
+    }
+}
+
+/// Check whether a header carries credentials, e.g. `Authorization`, `X-Amz-Security-Token`
+/// or the session in `Cookie`
+pub fn is_sensitive(key: &str) -> bool {
+    [
+        "authorization",
+        "proxy-authorization",
+        "x-amz-security-token",
+        "cookie",
+        "set-cookie",
+    ]
+    .iter()
+    .any(|sensitive| key.trim().eq_ignore_ascii_case(sensitive))
+}
+
+/// Redact the credentials of a header value, keeping the authentication scheme
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::auth::redact_value;
+///
+/// assert_eq!(redact_value("Bearer eyJhbGciOiJIUzI1NiJ9"), "Bearer [REDACTED]");
+/// assert_eq!(redact_value("secret"), "[REDACTED]");
+/// ```
+pub fn redact_value(value: &str) -> String {
+    match value.trim().split_once(' ') {
+        Some((scheme, _)) => format!("{scheme} {REDACTED}"),
+        None => REDACTED.to_string(),
+    }
+}
+
+/// Redact the credentials of a sensitive header value, the cookies keep their names and the
+/// `Set-Cookie` attributes are kept
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::auth::redact_header;
+///
+/// assert_eq!(redact_header("Cookie", "a=1; b=2"), "a=[REDACTED]; b=[REDACTED]");
+/// assert_eq!(
+///     redact_header("Set-Cookie", "session=abc; Path=/; HttpOnly"),
+///     "session=[REDACTED]; Path=/; HttpOnly"
+/// );
+/// assert_eq!(redact_header("Authorization", "Basic dXNlcjpwYXNz"), "Basic [REDACTED]");
+/// ```
+pub fn redact_header(key: &str, value: &str) -> String {
+    let redact_cookie = |pair: &str| match pair.trim().split_once('=') {
+        Some((name, _)) => format!("{name}={REDACTED}"),
+        None => REDACTED.to_string(),
+    };
+
+    match key.trim().to_ascii_lowercase().as_str() {
+        "cookie" => value
+            .split(';')
+            .filter(|pair| !pair.trim().is_empty())
+            .map(redact_cookie)
+            .collect::<Vec<_>>()
+            .join("; "),
+        "set-cookie" => {
+            let (pair, attributes) = value.split_once(';').unwrap_or((value, ""));
+            match attributes.trim() {
+                "" => redact_cookie(pair),
+                attributes => format!("{}; {attributes}", redact_cookie(pair)),
+            }
+        }
+        _ => redact_value(value),
+    }
+}
+
+/// Redact the credentials of every sensitive header in a block of raw header lines
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::auth::redact_headers;
+///
+/// let headers = "GET / HTTP/1.1\r\nAuthorization: Basic dXNlcjpwYXNz\r\nAccept: */*\r\n\r\n";
+///
+/// assert_eq!(
+///     redact_headers(headers),
+///     "GET / HTTP/1.1\r\nAuthorization: Basic [REDACTED]\r\nAccept: */*\r\n\r\n"
+/// );
+/// ```
+pub fn redact_headers(text: &str) -> Cow<'_, str> {
+    let sensitive = |line: &str| {
+        line.split_once(':')
+            .is_some_and(|(key, _)| is_sensitive(key))
+    };
+    if !text.lines().any(sensitive) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(
+        text.split_inclusive('\n')
+            .map(|line| match line.split_once(':') {
+                Some((key, value)) if is_sensitive(key) => {
+                    let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+                    format!("{key}: {}{ending}", redact_header(key, value))
+                }
+                _ => line.to_string(),
+            })
+            .collect(),
+    )
+}
+
+/// Redact the tokens of an OAuth2 token response, e.g. in the verbose output of the token request
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::auth::redact_tokens;
+///
+/// assert_eq!(
+///     redact_tokens(r#"{"access_token": "eyJhbGciOiJIUzI1NiJ9", "expires_in": 3600}"#),
+///     r#"{"access_token": "[REDACTED]", "expires_in": 3600}"#
+/// );
+/// ```
+pub fn redact_tokens(text: &str) -> Cow<'_, str> {
+    let mut redacted = Cow::Borrowed(text);
+    for key in ["\"access_token\"", "\"refresh_token\"", "\"id_token\""] {
+        let mut start = 0;
+        while let Some(found) = redacted[start..].find(key) {
+            let after = start + found + key.len();
+            let rest = &redacted[after..];
+            let value = rest.trim_start().strip_prefix(':').map(str::trim_start);
+            let Some(value) = value.and_then(|value| value.strip_prefix('"')) else {
+                start = after;
+                continue;
+            };
+
+            // The value ends at the next quote that is not escaped
+            let begin = redacted.len() - value.len();
+            let mut end = None;
+            let mut escaped = false;
+            for (index, c) in value.char_indices() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => {
+                        end = Some(begin + index);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let end = end.unwrap_or(redacted.len());
+
+            redacted.to_mut().replace_range(begin..end, REDACTED);
+            start = begin + REDACTED.len();
+        }
+    }
+    redacted
+}
+
+/// OAuth2 struct to fetch a bearer token with the client credentials grant before the request
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::auth::OAuth2;
+///
+/// let oauth2 = OAuth2 {
+///     token_url: "https://auth.example.com/oauth2/token".to_string(),
+///     client_id: "client".to_string(),
+///     client_secret: "hunter2".to_string(),
+///     scope: Some("read write".to_string()),
+/// };
+///
+/// assert_eq!(oauth2.form(), "grant_type=client_credentials&scope=read%20write");
+/// assert!(!format!("{oauth2:?}").contains("hunter2"));
+/// ```
+///
+#[derive(Clone, PartialEq)]
+pub struct OAuth2 {
+    /// URL of the token endpoint
+    pub token_url: String,
+    /// Client ID
+    pub client_id: String,
+    /// Client secret
+    pub client_secret: String,
+    /// Space separated scopes to request
+    pub scope: Option<String>,
+}
+
+impl core::fmt::Debug for OAuth2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OAuth2")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &REDACTED)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+/// Token struct to store a fetched access token and the `Stat` of its request
+#[derive(Clone)]
+pub struct Token {
+    /// Access token to send as the bearer token
+    pub access_token: String,
+    /// When the token expires, `None` when the endpoint did not tell
+    pub expires_at: Option<Instant>,
+    /// Response information of the token request
+    pub stat: Stat,
+}
+
+impl Token {
+    /// Check whether the token can no longer be used
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| Instant::now() >= expires_at)
+    }
+}
+
+impl OAuth2 {
+    /// Form body of the token request
+    pub fn form(&self) -> String {
+        let mut form = "grant_type=client_credentials".to_string();
+        if let Some(scope) = &self.scope {
+            form.push_str("&scope=");
+            form.push_str(&crate::encoding::percent_encode(scope.as_bytes(), true));
+        }
+        form
+    }
+
+    /// Fetch a token from the token endpoint, authenticating with the client credentials
+    /// in HTTP Basic authentication. The connection settings of the configuration, such as
+    /// the proxy, TLS options and timeouts, apply to the token request as well. The cookies
+    /// and the `--resolve` and `--connect-to` overrides are meant for the API host and do not.
+    pub fn fetch(&self, conf: &Config) -> crate::Result<Token> {
+        let encode = |value: &str| crate::encoding::percent_encode(value.as_bytes(), true);
+        let config = Config {
+            url: self.token_url.as_str().into(),
+            method: Method::Post,
+            request_headers: vec![
+                Header {
+                    key: "Content-Type".to_string(),
+                    value: "application/x-www-form-urlencoded".to_string(),
+                },
+                Header {
+                    key: "Accept".to_string(),
+                    value: "application/json".to_string(),
+                },
+            ],
+            request_body: Some(self.form().into()),
+            follow_redirects: false,
+            count: 1,
+            user: Some(
+                format!(
+                    "{}:{}",
+                    encode(&self.client_id),
+                    encode(&self.client_secret)
+                )
+                .into(),
+            ),
+            auth_method: AuthMethod::Basic,
+            bearer: None,
+            netrc: false,
+            netrc_file: None,
+            aws_sigv4: None,
+            oauth2: None,
+            cookie: None,
+            cookie_jar: None,
+            resolve: vec![],
+            connect_to: vec![],
+            form: vec![],
+            sink: Sink::Memory,
+            ..conf.clone()
+        };
+        let mut stat = crate::network::send_request(&config)?;
+
+        let status = stat.response_status_code.unwrap_or_default();
+        let token = match (200..300).contains(&status) {
+            true => parse_token(&stat.utf8_response_body().unwrap_or_default()),
+            false => Err(format!("Token request failed with status {status}")),
+        };
+
+        match token {
+            Ok((access_token, expires_in)) => Ok(Token {
+                access_token,
+                expires_at: expires_in.map(|seconds| Instant::now() + Duration::from_secs(seconds)),
+                stat,
+            }),
+            Err(message) => {
+                stat.error = Some(message);
+                let source = curl::Error::new(curl_sys::CURLE_LOGIN_DENIED);
+                Err(TransferError { stat, source }.into())
+            }
+        }
+    }
+}
+
+/// Get the access token and its lifetime in seconds from the token response
+#[cfg(feature = "serde")]
+fn parse_token(body: &str) -> Result<(String, Option<u64>), String> {
+    let response = serde_json::from_str::<serde_json::Value>(body)
+        .map_err(|e| format!("Invalid token response: {e}"))?;
+    let access_token = response["access_token"]
+        .as_str()
+        .ok_or("Token response has no access_token")?;
+
+    Ok((access_token.to_string(), response["expires_in"].as_u64()))
+}
+
+/// Get the access token and its lifetime in seconds from the token response
+#[cfg(not(feature = "serde"))]
+fn parse_token(_body: &str) -> Result<(String, Option<u64>), String> {
+    Err("Reading the token response requires the `serde` feature".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_sensitive() {
+        assert!(is_sensitive("Authorization"));
+        assert!(is_sensitive("proxy-authorization"));
+        assert!(is_sensitive("Cookie"));
+        assert!(is_sensitive("set-cookie"));
+        assert!(!is_sensitive("WWW-Authenticate"));
+        assert!(!is_sensitive("Content-Type"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_parse_token() {
+        assert_eq!(
+            parse_token(r#"{"access_token":"abc","token_type":"Bearer","expires_in":3600}"#),
+            Ok(("abc".to_string(), Some(3600)))
+        );
+        assert_eq!(
+            parse_token(r#"{"access_token":"abc"}"#),
+            Ok(("abc".to_string(), None))
+        );
+        assert!(parse_token(r#"{"error":"invalid_client"}"#).is_err());
+        assert!(parse_token("<html>").is_err());
+    }
+
+    #[test]
+    fn test_token_is_expired() {
+        let token = Token {
+            access_token: "abc".to_string(),
+            expires_at: None,
+            stat: Stat::default(),
+        };
+        assert!(!token.is_expired());
+
+        let token = Token {
+            expires_at: Some(Instant::now()),
+            ..token
+        };
+        assert!(token.is_expired());
+    }
+
+    #[test]
+    fn test_redact_tokens() {
+        let text = "HTTP body without tokens";
+        assert!(matches!(redact_tokens(text), Cow::Borrowed(_)));
+
+        assert_eq!(
+            redact_tokens(r#"{"access_token":"a\"b","refresh_token" : "c","id_token":"d"}"#),
+            r#"{"access_token":"[REDACTED]","refresh_token" : "[REDACTED]","id_token":"[REDACTED]"}"#
+        );
+        assert_eq!(
+            redact_tokens(r#"{"access_token":null,"x":"access_token"}"#),
+            r#"{"access_token":null,"x":"access_token"}"#
+        );
+        assert_eq!(
+            redact_tokens(r#"{"access_token":"cut off"#),
+            r#"{"access_token":"[REDACTED]"#
+        );
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let text = "Accept: */*\n";
+        assert!(matches!(redact_headers(text), Cow::Borrowed(_)));
+
+        let text = "proxy-authorization: NTLM TlRMTVNTUAABAAAA\nauthorization:token";
+        assert_eq!(
+            redact_headers(text),
+            "proxy-authorization: NTLM [REDACTED]\nauthorization: [REDACTED]"
+        );
+
+        let text = "Cookie: a=1; b=2\r\nSet-Cookie: session=abc; Secure\r\n";
+        assert_eq!(
+            redact_headers(text),
+            "Cookie: a=[REDACTED]; b=[REDACTED]\r\nSet-Cookie: session=[REDACTED]; Secure\r\n"
+        );
+    }
+}
@@ -0,0 +1,519 @@
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// Enum for the sources of the request body
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::body::Body;
+/// use std::io::Read;
+///
+/// let body = Body::from("hello");
+/// let mut reader = body.reader().unwrap();
+/// let mut buffer = [0; 3];
+///
+/// assert_eq!(body.len(), Some(5));
+/// assert_eq!(reader.read(&mut buffer).unwrap(), 3);
+/// assert_eq!(reader.position(), 3);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body<'a> {
+    /// Bytes in memory
+    Bytes(Cow<'a, [u8]>),
+    /// File streamed from disk
+    File(PathBuf),
+    /// Standard input streamed as it is read, it can only be sent once and not rewound
+    Stdin,
+}
+
+impl<'a> Body<'a> {
+    /// Size of the body in bytes, `None` when it is not known upfront
+    pub fn len(&self) -> Option<u64> {
+        match self {
+            Body::Bytes(bytes) => Some(bytes.len() as u64),
+            Body::File(path) => std::fs::metadata(path).ok().map(|metadata| metadata.len()),
+            Body::Stdin => None,
+        }
+    }
+
+    /// Check whether the body is known to be empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Open a reader from the start of the body
+    pub fn reader(&self) -> std::io::Result<BodyReader<'_>> {
+        let source = match self {
+            Body::Bytes(bytes) => Source::Bytes(bytes),
+            Body::File(path) => Source::File(std::fs::File::open(path)?),
+            Body::Stdin => Source::Stdin(std::io::stdin()),
+        };
+
+        Ok(BodyReader {
+            source,
+            position: 0,
+        })
+    }
+}
+
+impl Body<'static> {
+    /// Body of a data argument, `@path` streams a file and `@-` streams standard input
+    pub fn from_arg(value: &str) -> Self {
+        match value.strip_prefix('@') {
+            Some("-") => Body::Stdin,
+            Some(path) => Body::File(path.into()),
+            None => Body::from(value.to_string()),
+        }
+    }
+
+    /// Body of the data arguments in order, joined with `&` the same as curl, except for
+    /// consecutive JSON arguments which are joined as they are and have to parse as one JSON
+    /// document with the `serde` feature. A single data argument of
+    /// a file or standard input is streamed, anything else is read into memory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cetar::body::{Body, Data};
+    ///
+    /// let data = vec![
+    ///     Data::Text("name=cetar".to_string()),
+    ///     Data::UrlEncode("query=a b&c".to_string()),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Body::from_data(&data).unwrap(),
+    ///     Some(Body::from("name=cetar&query=a%20b%26c"))
+    /// );
+    /// ```
+    pub fn from_data(data: &[Data]) -> crate::Result<Option<Self>> {
+        if let [Data::Text(value) | Data::Binary(value)] = data {
+            return Ok(Some(Body::from_arg(value)));
+        }
+
+        let mut bytes = vec![];
+        // Each run of consecutive JSON arguments is one JSON document
+        let mut json = None;
+        for (index, item) in data.iter().enumerate() {
+            let joined = matches!(
+                (index.checked_sub(1).map(|previous| &data[previous]), item),
+                (Some(Data::Json(_)), Data::Json(_))
+            );
+            if index > 0 && !joined {
+                bytes.push(b'&');
+                if let Some(json) = json.take() {
+                    validate_json(&bytes[json..bytes.len() - 1])?;
+                }
+            }
+
+            if let Data::Json(_) = item {
+                json.get_or_insert(bytes.len());
+            }
+            bytes.extend(item.bytes()?);
+        }
+        if let Some(json) = json {
+            validate_json(&bytes[json..])?;
+        }
+
+        Ok((!data.is_empty()).then(|| Body::from(bytes)))
+    }
+}
+
+/// Check that the JSON payload parses before sending it
+#[cfg(feature = "serde")]
+fn validate_json(json: &[u8]) -> crate::Result<()> {
+    serde_json::from_slice::<serde::de::IgnoredAny>(json)
+        .map(|_| ())
+        .map_err(|e| crate::Error::InvalidInput(format!("Invalid JSON data: {e}")))
+}
+
+/// Check that the JSON payload parses before sending it, which requires the `serde` feature
+#[cfg(not(feature = "serde"))]
+fn validate_json(_json: &[u8]) -> crate::Result<()> {
+    Ok(())
+}
+
+/// Enum for the request data arguments, the same as curl's data options
+#[derive(Debug, Clone, PartialEq)]
+pub enum Data {
+    /// Data of `-d`, `@path` and `@-` read a file and standard input. Unlike curl the
+    /// carriage returns and newlines of a file are kept, so it is sent the same as `Binary`
+    Text(String),
+    /// Data of `--data-binary` sent as it is, `@path` and `@-` read a file and standard input
+    Binary(String),
+    /// Data percent-encoded in the `content`, `=content`, `name=content`, `@path`
+    /// or `name@path` format
+    UrlEncode(String),
+    /// JSON data sent as it is, `@path` and `@-` read a file and standard input
+    Json(String),
+}
+
+impl Data {
+    /// Bytes of the data, reading the file or standard input it refers to
+    fn bytes(&self) -> std::io::Result<Vec<u8>> {
+        match self {
+            Data::Text(value) | Data::Binary(value) | Data::Json(value) => read_arg(value),
+            Data::UrlEncode(value) => {
+                let encode = |value: &[u8]| crate::encoding::percent_encode(value, true);
+                let encoded = match value.find(['=', '@']).map(|index| value.split_at(index)) {
+                    Some(("", content)) if content.starts_with('=') => {
+                        encode(&content.as_bytes()[1..])
+                    }
+                    Some((name, content)) if content.starts_with('=') => {
+                        format!("{name}={}", encode(&content.as_bytes()[1..]))
+                    }
+                    Some(("", path)) => encode(&read_arg(path)?),
+                    Some((name, path)) => format!("{name}={}", encode(&read_arg(path)?)),
+                    None => encode(value.as_bytes()),
+                };
+                Ok(encoded.into_bytes())
+            }
+        }
+    }
+}
+
+/// Read a data argument, `@path` reads a file and `@-` reads standard input
+fn read_arg(value: &str) -> std::io::Result<Vec<u8>> {
+    match value.strip_prefix('@') {
+        Some("-") => {
+            let mut buffer = vec![];
+            std::io::stdin().read_to_end(&mut buffer)?;
+            Ok(buffer)
+        }
+        Some(path) => std::fs::read(path),
+        None => Ok(value.as_bytes().to_vec()),
+    }
+}
+
+impl<'a> From<&'a str> for Body<'a> {
+    fn from(value: &'a str) -> Self {
+        Body::Bytes(Cow::Borrowed(value.as_bytes()))
+    }
+}
+
+impl From<String> for Body<'_> {
+    fn from(value: String) -> Self {
+        Body::Bytes(Cow::Owned(value.into_bytes()))
+    }
+}
+
+impl From<Vec<u8>> for Body<'_> {
+    fn from(value: Vec<u8>) -> Self {
+        Body::Bytes(Cow::Owned(value))
+    }
+}
+
+/// Enum for the values of a multipart form part
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormValue {
+    /// Text value
+    Text(String),
+    /// File uploaded as an attachment, `@path` in curl
+    File(PathBuf),
+    /// Text value read from a file, `<path` in curl
+    FileContent(PathBuf),
+}
+
+/// FormPart struct to store a part of a `multipart/form-data` body, in the same
+/// syntax as curl's `-F`
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::body::{FormPart, FormValue};
+/// use std::str::FromStr;
+///
+/// let part = FormPart::from_str("avatar=@photo.png;type=image/png;filename=me.png").unwrap();
+///
+/// assert_eq!(part.name, "avatar");
+/// assert_eq!(part.value, FormValue::File("photo.png".into()));
+/// assert_eq!(part.content_type.as_deref(), Some("image/png"));
+/// assert_eq!(part.filename.as_deref(), Some("me.png"));
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormPart {
+    /// Field name
+    pub name: String,
+    /// Field value
+    pub value: FormValue,
+    /// Content type of the part, guessed by curl from the file extension when `None`
+    pub content_type: Option<String>,
+    /// File name sent instead of the name of the uploaded file
+    pub filename: Option<String>,
+}
+
+impl std::str::FromStr for FormPart {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s
+            .split_once('=')
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid form part, please use name=value"))?;
+
+        let mut content_type = None;
+        let mut filename = None;
+        let mut parts = value.split(';');
+        let mut value = parts.next().unwrap_or_default().to_string();
+        for attribute in parts {
+            match attribute.trim().split_once('=') {
+                Some(("type", mime)) => content_type = Some(mime.to_string()),
+                Some(("filename", name)) => filename = Some(name.to_string()),
+                // Not an attribute, the value itself contains a semicolon
+                _ => {
+                    value.push(';');
+                    value.push_str(attribute);
+                }
+            }
+        }
+
+        let value = match (value.strip_prefix('@'), value.strip_prefix('<')) {
+            (Some(path), _) => FormValue::File(path.into()),
+            (_, Some(path)) => FormValue::FileContent(path.into()),
+            _ => FormValue::Text(value),
+        };
+        if matches!(&value, FormValue::File(path) | FormValue::FileContent(path) if path.as_os_str().is_empty())
+        {
+            anyhow::bail!("Invalid form part {name}, the file path is empty");
+        }
+
+        Ok(Self {
+            name: name.trim().to_string(),
+            value,
+            content_type,
+            filename,
+        })
+    }
+}
+
+enum Source<'a> {
+    Bytes(&'a [u8]),
+    File(std::fs::File),
+    Stdin(std::io::Stdin),
+}
+
+/// BodyReader struct to read a `Body` while tracking the position, so curl can rewind it
+/// for redirects and authentication retries
+pub struct BodyReader<'a> {
+    source: Source<'a>,
+    position: u64,
+}
+
+impl BodyReader<'_> {
+    /// Number of bytes read from the start of the body
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl Read for BodyReader<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let len = match &mut self.source {
+            Source::Bytes(bytes) => {
+                let start = (self.position as usize).min(bytes.len());
+                let len = buffer.len().min(bytes.len() - start);
+                buffer[..len].copy_from_slice(&bytes[start..start + len]);
+                len
+            }
+            Source::File(file) => file.read(buffer)?,
+            Source::Stdin(stdin) => stdin.read(buffer)?,
+        };
+
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+impl Seek for BodyReader<'_> {
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+        let unsupported = || {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Standard input cannot be rewound",
+            )
+        };
+
+        self.position = match (&mut self.source, position) {
+            (Source::File(file), position) => file.seek(position)?,
+            (Source::Stdin(_), SeekFrom::Current(0)) => self.position,
+            (Source::Stdin(_), SeekFrom::Start(offset)) if offset == self.position => offset,
+            (Source::Stdin(_), _) => return Err(unsupported()),
+            (Source::Bytes(bytes), position) => {
+                let target = match position {
+                    SeekFrom::Start(offset) => Some(offset),
+                    SeekFrom::End(offset) => (bytes.len() as u64).checked_add_signed(offset),
+                    SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+                };
+                target.ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Seek before the start of the body",
+                    )
+                })?
+            }
+        };
+
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_bytes_reader_rewind() {
+        let body = Body::from(vec![0u8, 159, 146, 150, 255]);
+        let mut reader = body.reader().unwrap();
+        let mut buffer = [0; 2];
+
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [146, 150]);
+        assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+
+        let mut all = vec![];
+        reader.read_to_end(&mut all).unwrap();
+        assert_eq!(all, vec![0u8, 159, 146, 150, 255]);
+        assert_eq!(reader.position(), 5);
+        assert!(reader.seek(SeekFrom::Current(-6)).is_err());
+    }
+
+    #[test]
+    fn test_file_reader() {
+        let path = std::env::temp_dir().join("cetar-body-test.bin");
+        let data = (0..=255u8).cycle().take(100_000).collect::<Vec<_>>();
+        std::fs::write(&path, &data).unwrap();
+
+        let body = Body::File(path.clone());
+        assert_eq!(body.len(), Some(100_000));
+
+        let mut reader = body.reader().unwrap();
+        let mut read = vec![];
+        reader.read_to_end(&mut read).unwrap();
+        reader.seek(SeekFrom::Start(0)).unwrap();
+        let mut again = vec![];
+        reader.read_to_end(&mut again).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read, data);
+        assert_eq!(again, data);
+        assert!(Body::File("/nonexistent/body".into()).reader().is_err());
+    }
+
+    #[test]
+    fn test_form_part_from_str() {
+        let part = FormPart::from_str("name=John; Doe").unwrap();
+        assert_eq!(part.value, FormValue::Text("John; Doe".to_string()));
+        assert_eq!(part.content_type, None);
+
+        let part = FormPart::from_str("notes=<notes.txt;type=text/plain").unwrap();
+        assert_eq!(part.value, FormValue::FileContent("notes.txt".into()));
+        assert_eq!(part.content_type.as_deref(), Some("text/plain"));
+
+        let part = FormPart::from_str("empty=").unwrap();
+        assert_eq!(part.value, FormValue::Text(String::new()));
+
+        assert!(FormPart::from_str("novalue").is_err());
+        assert!(FormPart::from_str("=value").is_err());
+        assert!(FormPart::from_str("file=@").is_err());
+    }
+
+    #[test]
+    fn test_url_encode() {
+        let path = std::env::temp_dir().join("cetar-data-urlencode.txt");
+        std::fs::write(&path, "a b").unwrap();
+        let table = vec![
+            ("hello world", "hello%20world"),
+            ("=a=b", "a%3Db"),
+            ("name=a&b", "name=a%26b"),
+            ("name=", "name="),
+            ("e=mc@2", "e=mc%402"),
+        ];
+
+        for (value, expected) in table {
+            let data = Data::UrlEncode(value.to_string());
+            assert_eq!(data.bytes().unwrap(), expected.as_bytes(), "{value}");
+        }
+
+        let data = Data::UrlEncode(format!("file@{}", path.display()));
+        assert_eq!(data.bytes().unwrap(), b"file=a%20b");
+        let data = Data::UrlEncode(format!("@{}", path.display()));
+        assert_eq!(data.bytes().unwrap(), b"a%20b");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_from_data() {
+        assert_eq!(Body::from_data(&[]).unwrap(), None);
+        assert_eq!(
+            Body::from_data(&[Data::Text("@-".to_string())]).unwrap(),
+            Some(Body::Stdin)
+        );
+        assert_eq!(
+            Body::from_data(&[Data::Text("@body.bin".to_string())]).unwrap(),
+            Some(Body::File("body.bin".into()))
+        );
+        assert_eq!(
+            Body::from_data(&[Data::Binary("@body.bin".to_string())]).unwrap(),
+            Some(Body::File("body.bin".into()))
+        );
+        assert_eq!(
+            Body::from_data(&[
+                Data::Text("a=1".to_string()),
+                Data::Binary("b=2".to_string())
+            ])
+            .unwrap(),
+            Some(Body::from("a=1&b=2"))
+        );
+        assert_eq!(
+            Body::from_data(&[
+                Data::Json("{\"a\":".to_string()),
+                Data::Json("1}".to_string())
+            ])
+            .unwrap(),
+            Some(Body::from("{\"a\":1}"))
+        );
+        assert!(Body::from_data(&[
+            Data::Text("@/nonexistent/data".to_string()),
+            Data::Text("a".to_string())
+        ])
+        .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_data_invalid_json() {
+        let result = Body::from_data(&[Data::Json("{\"a\":".to_string())]);
+        assert!(matches!(result, Err(crate::Error::InvalidInput(_))));
+
+        // Split by other data, each run of JSON arguments has to parse on its own
+        let result = Body::from_data(&[
+            Data::Json("[1,".to_string()),
+            Data::Text("a=1".to_string()),
+            Data::Json("2]".to_string()),
+        ]);
+        assert!(matches!(result, Err(crate::Error::InvalidInput(_))));
+
+        let result = Body::from_data(&[
+            Data::Json("{\"a\":".to_string()),
+            Data::Json("1}".to_string()),
+            Data::Text("b=2".to_string()),
+            Data::Json("[]".to_string()),
+        ]);
+        assert_eq!(result.unwrap(), Some(Body::from("{\"a\":1}&b=2&[]")));
+    }
+
+    #[test]
+    fn test_stdin_len() {
+        assert_eq!(Body::Stdin.len(), None);
+        assert!(!Body::Stdin.is_empty());
+        assert!(Body::from("").is_empty());
+    }
+}
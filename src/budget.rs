@@ -0,0 +1,286 @@
+use std::time::Duration;
+
+use crate::network::{Phase, Stat};
+
+/// Parse a human readable duration, e.g. `250ms`, `1.5s`, `2m` or `30d`.
+/// Available units: `us`, `ms`, `s`, `m`, `h` and `d`, a bare number is in milliseconds.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::budget::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("50ms").unwrap(), Duration::from_millis(50));
+/// assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+/// assert_eq!(parse_duration("100").unwrap(), Duration::from_millis(100));
+/// ```
+///
+pub fn parse_duration(value: &str) -> anyhow::Result<Duration> {
+    let value = value.trim();
+    let index = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(index);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid duration: {value}"))?;
+
+    let seconds = match unit.trim() {
+        "us" => number / 1_000_000.0,
+        "" | "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        "d" => number * 86400.0,
+        _ => anyhow::bail!("Invalid duration unit in {value}, please use us, ms, s, m, h or d"),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow::anyhow!("Invalid duration: {value}"))
+}
+
+/// Enum for the measurements a budget can be set on
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::budget::Metric;
+/// use cetar::network::Phase;
+/// use std::str::FromStr;
+///
+/// assert_eq!(Metric::from_str("tls").unwrap(), Metric::Phase(Phase::TlsHandshake));
+/// assert_eq!(Metric::from_str("ttfb").unwrap(), Metric::TimeToFirstByte);
+/// ```
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Metric {
+    /// Duration of a single phase
+    Phase(Phase),
+    /// Time until the first byte of the response was received
+    TimeToFirstByte,
+}
+
+impl Metric {
+    /// Human readable name of the metric
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Phase(phase) => phase.name(),
+            Metric::TimeToFirstByte => "Time To First Byte",
+        }
+    }
+
+    /// Get the measured duration from the `Stat`, if applicable
+    pub fn measure(&self, stat: &Stat) -> Option<Duration> {
+        match self {
+            Metric::Phase(phase) => stat.phase(*phase),
+            Metric::TimeToFirstByte => Some(stat.start_transfer),
+        }
+    }
+}
+
+impl std::str::FromStr for Metric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dns" | "dns_lookup" => Ok(Self::Phase(Phase::DnsLookup)),
+            "tcp" | "tcp_handshake" => Ok(Self::Phase(Phase::TcpHandshake)),
+            "tls" | "tls_handshake" => Ok(Self::Phase(Phase::TlsHandshake)),
+            "server" | "server_processing" => Ok(Self::Phase(Phase::ServerProcessing)),
+            "transfer" | "content_transfer" => Ok(Self::Phase(Phase::ContentTransfer)),
+            "total" => Ok(Self::Phase(Phase::Total)),
+            "ttfb" => Ok(Self::TimeToFirstByte),
+            _ => Err(anyhow::anyhow!(
+                "Invalid phase, please use dns, tcp, tls, server, transfer, total or ttfb"
+            )),
+        }
+    }
+}
+
+/// Budget struct to store the maximum allowed duration of a metric
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::budget::{Budget, Metric};
+/// use cetar::network::Phase;
+/// use std::str::FromStr;
+/// use std::time::Duration;
+///
+/// let budget = Budget::from_str("tls=50ms").unwrap();
+///
+/// assert_eq!(budget.metric, Metric::Phase(Phase::TlsHandshake));
+/// assert_eq!(budget.limit, Duration::from_millis(50));
+/// ```
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Budget {
+    /// Measured metric
+    pub metric: Metric,
+    /// Maximum allowed duration
+    pub limit: Duration,
+}
+
+impl std::str::FromStr for Budget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((metric, limit)) => Ok(Self {
+                metric: metric.parse()?,
+                limit: parse_duration(limit)?,
+            }),
+            None => anyhow::bail!("Invalid budget format, please use phase=duration"),
+        }
+    }
+}
+
+/// Violation struct to store an exceeded budget
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Violation {
+    /// Index of the request the violation happened in, starting at 0
+    pub run: usize,
+    /// Exceeded budget
+    pub budget: Budget,
+    /// Measured duration
+    pub actual: Duration,
+}
+
+/// Check the budgets against every `Stat` and return the violations.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::budget::{check, Budget};
+/// use cetar::network::Stat;
+/// use std::str::FromStr;
+/// use std::time::Duration;
+///
+/// let budgets = vec![Budget::from_str("total=100ms").unwrap()];
+/// let stats = vec![Stat {
+///     total: Duration::from_millis(150),
+///     ..Default::default()
+/// }];
+///
+/// let violations = check(&budgets, &stats);
+///
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].actual, Duration::from_millis(150));
+/// ```
+///
+pub fn check(budgets: &[Budget], stats: &[Stat]) -> Vec<Violation> {
+    stats
+        .iter()
+        .enumerate()
+        .flat_map(|(run, stat)| {
+            budgets.iter().filter_map(move |budget| {
+                budget
+                    .metric
+                    .measure(stat)
+                    .filter(|actual| *actual > budget.limit)
+                    .map(|actual| Violation {
+                        run,
+                        budget: *budget,
+                        actual,
+                    })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_duration() {
+        let table = vec![
+            ("500us", Duration::from_micros(500)),
+            ("50ms", Duration::from_millis(50)),
+            ("50", Duration::from_millis(50)),
+            ("1.5s", Duration::from_millis(1500)),
+            ("2m", Duration::from_secs(120)),
+            ("1h", Duration::from_secs(3600)),
+            ("30d", Duration::from_secs(30 * 86400)),
+            (" 10 ms ", Duration::from_millis(10)),
+        ];
+
+        for (value, expected) in table {
+            assert_eq!(parse_duration(value).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        for value in ["", "ms", "10w", "-5ms", "1.2.3s"] {
+            assert!(parse_duration(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_metric_from_str() {
+        let table = vec![
+            ("dns", Metric::Phase(Phase::DnsLookup)),
+            ("tcp", Metric::Phase(Phase::TcpHandshake)),
+            ("TLS", Metric::Phase(Phase::TlsHandshake)),
+            ("server", Metric::Phase(Phase::ServerProcessing)),
+            ("transfer", Metric::Phase(Phase::ContentTransfer)),
+            ("total", Metric::Phase(Phase::Total)),
+            ("ttfb", Metric::TimeToFirstByte),
+        ];
+
+        for (value, expected) in table {
+            assert_eq!(Metric::from_str(value).unwrap(), expected);
+        }
+
+        assert!(Metric::from_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_budget_from_str_invalid() {
+        assert!(Budget::from_str("tls").is_err());
+        assert!(Budget::from_str("tls=fast").is_err());
+        assert!(Budget::from_str("invalid=50ms").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let budgets = vec![
+            Budget::from_str("tls=50ms").unwrap(),
+            Budget::from_str("ttfb=200ms").unwrap(),
+        ];
+        let stats = vec![
+            Stat {
+                connect: Duration::from_millis(10),
+                app_connect: Duration::from_millis(40),
+                start_transfer: Duration::from_millis(100),
+                ..Default::default()
+            },
+            Stat {
+                connect: Duration::from_millis(10),
+                app_connect: Duration::from_millis(70),
+                start_transfer: Duration::from_millis(250),
+                ..Default::default()
+            },
+        ];
+
+        let violations = check(&budgets, &stats);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].run, 1);
+        assert_eq!(violations[0].budget, budgets[0]);
+        assert_eq!(violations[0].actual, Duration::from_millis(60));
+        assert_eq!(violations[1].budget.metric, Metric::TimeToFirstByte);
+        assert_eq!(violations[1].actual, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_check_not_applicable() {
+        let budgets = vec![Budget::from_str("tls=1ms").unwrap()];
+        let stats = vec![Stat::default()];
+
+        assert!(check(&budgets, &stats).is_empty());
+    }
+}
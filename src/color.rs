@@ -37,7 +37,7 @@ macro_rules! print_error {
 
 /// Enum for ANSI color codes
 ///
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub enum Color {
     Black = 30,
     Red = 31,
@@ -46,15 +46,10 @@ pub enum Color {
     Blue = 34,
     Magenta = 35,
     Cyan = 36,
+    #[default]
     White = 37,
 }
 
-impl Default for Color {
-    fn default() -> Self {
-        Self::White
-    }
-}
-
 impl TryFrom<&str> for Color {
     type Error = anyhow::Error;
 
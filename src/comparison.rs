@@ -0,0 +1,276 @@
+use crate::dns::{IpFamily, Resolve};
+use crate::network::{send_request, Config, HttpVersion, Stat};
+
+/// Variant struct to store the result of one side of a comparison
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::comparison::{stats, Variant};
+/// use cetar::network::Stat;
+/// use cetar::Error;
+///
+/// let mut variants = vec![
+///     Variant {
+///         label: "HTTP/1.1".to_string(),
+///         result: Ok(Stat::default()),
+///     },
+///     Variant {
+///         label: "HTTP/3".to_string(),
+///         result: Err(Error::InvalidInput("HTTP/3 is not supported".to_string())),
+///     },
+/// ];
+///
+/// assert_eq!(stats(&mut variants).len(), 1);
+/// ```
+///
+#[derive(Debug)]
+pub struct Variant {
+    /// Name of the variant, e.g. `HTTP/2`
+    pub label: String,
+    /// Response information, or the error when the request failed
+    pub result: crate::Result<Stat>,
+}
+
+/// Get the `Stat` of every successful variant, in order, labelled with `Stat::variant`.
+/// The response bodies are moved out of the variants instead of copied, the variants keep
+/// their timings.
+pub fn stats(variants: &mut [Variant]) -> Vec<Stat> {
+    variants
+        .iter_mut()
+        .filter_map(|variant| Some((&variant.label, variant.result.as_mut().ok()?)))
+        .map(|(label, stat)| {
+            let response_body = std::mem::take(&mut stat.response_body);
+            Stat {
+                response_body,
+                variant: Some(label.clone()),
+                ..stat.clone()
+            }
+        })
+        .collect()
+}
+
+/// Send the request once with each of `HttpVersion::COMPARED`, a version
+/// the server or the linked libcurl does not support ends up as an error.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use cetar::comparison::http_versions;
+/// use cetar::network::Config;
+///
+/// let config = Config {
+///     url: "https://example.com".into(),
+///     ..Default::default()
+/// };
+///
+/// for variant in http_versions(&config) {
+///     println!("{}: {:?}", variant.label, variant.result.map(|stat| stat.total));
+/// }
+/// ```
+pub fn http_versions(config: &Config) -> Vec<Variant> {
+    HttpVersion::COMPARED
+        .iter()
+        .map(|version| {
+            let config = Config {
+                http_version: *version,
+                ..config.clone()
+            };
+
+            Variant {
+                label: version.name().to_string(),
+                result: send_request(&config),
+            }
+        })
+        .collect()
+}
+
+/// Send the request once over IPv4 and once over IPv6, a host without
+/// addresses of a family ends up as an error.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use cetar::comparison::ip_families;
+/// use cetar::network::Config;
+///
+/// let config = Config {
+///     url: "https://example.com".into(),
+///     ..Default::default()
+/// };
+///
+/// for variant in ip_families(&config) {
+///     println!("{}: {:?}", variant.label, variant.result.map(|stat| stat.total));
+/// }
+/// ```
+pub fn ip_families(config: &Config) -> Vec<Variant> {
+    IpFamily::COMPARED
+        .iter()
+        .map(|family| {
+            let config = Config {
+                ip_family: *family,
+                ..config.clone()
+            };
+
+            Variant {
+                label: family.name().to_string(),
+                result: send_request(&config),
+            }
+        })
+        .collect()
+}
+
+/// Resolve the URL's host and send the request once pinned to each of its addresses,
+/// so a single bad node behind round-robin DNS stands out.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use cetar::comparison::ip_addresses;
+/// use cetar::network::Config;
+///
+/// let config = Config {
+///     url: "https://example.com".into(),
+///     ..Default::default()
+/// };
+///
+/// for variant in ip_addresses(&config).unwrap() {
+///     println!("{}: {:?}", variant.label, variant.result.map(|stat| stat.total));
+/// }
+/// ```
+pub fn ip_addresses(config: &Config) -> crate::Result<Vec<Variant>> {
+    let resolved = crate::dns::resolve_url(&config.url)?;
+
+    Ok(resolved
+        .addresses
+        .iter()
+        .filter(|address| config.ip_family.matches(address))
+        .map(|address| {
+            let mut resolve = config.resolve.clone();
+            resolve.push(Resolve {
+                addresses: vec![address.clone()],
+                ..resolved.clone()
+            });
+            let config = Config {
+                resolve,
+                ..config.clone()
+            };
+
+            Variant {
+                label: address.trim_matches(['[', ']']).to_string(),
+                result: send_request(&config),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[test]
+    fn test_stats_moves_body() {
+        let mut variants = vec![Variant {
+            label: "IPv4".to_string(),
+            result: Ok(Stat {
+                response_body: b"body".to_vec(),
+                total: std::time::Duration::from_millis(5),
+                ..Default::default()
+            }),
+        }];
+
+        let stats = stats(&mut variants);
+
+        assert_eq!(stats[0].response_body, b"body");
+        assert_eq!(stats[0].variant.as_deref(), Some("IPv4"));
+        let variant = variants[0].result.as_ref().unwrap();
+        assert!(variant.response_body.is_empty());
+        assert_eq!(variant.total, stats[0].total);
+    }
+
+    #[test]
+    fn test_http_versions() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200);
+        });
+
+        let config = Config {
+            url: server.url("/").into(),
+            ..Default::default()
+        };
+        let mut variants = http_versions(&config);
+        let labels = variants
+            .iter()
+            .map(|variant| variant.label.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, vec!["HTTP/1.0", "HTTP/1.1", "HTTP/2", "HTTP/3"]);
+        assert_eq!(
+            variants[0].result.as_ref().unwrap().http_version.as_deref(),
+            Some("1.0")
+        );
+        assert!(variants[1].result.is_ok());
+        assert!(!stats(&mut variants).is_empty());
+        assert!(mock.hits() >= 2);
+    }
+
+    #[test]
+    fn test_ip_addresses() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200);
+        });
+
+        let config = Config {
+            url: format!("http://localhost:{}/", server.port()).into(),
+            ..Default::default()
+        };
+        let variants = ip_addresses(&config).unwrap();
+        let variant = variants
+            .iter()
+            .find(|variant| variant.label == "127.0.0.1")
+            .unwrap();
+
+        assert_eq!(
+            variant.result.as_ref().unwrap().ip_address.as_deref(),
+            Some("127.0.0.1")
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_ip_families() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200);
+        });
+
+        let config = Config {
+            url: format!("http://localhost:{}/", server.port()).into(),
+            ..Default::default()
+        };
+        let variants = ip_families(&config);
+
+        assert_eq!(variants[0].label, "IPv4");
+        assert_eq!(
+            variants[0].result.as_ref().unwrap().ip_address.as_deref(),
+            Some("127.0.0.1")
+        );
+        assert_eq!(variants[1].label, "IPv6");
+        mock.assert();
+
+        let config = Config {
+            ip_family: IpFamily::V6,
+            ..config
+        };
+        assert!(ip_addresses(&config)
+            .unwrap()
+            .iter()
+            .all(|variant| variant.label.contains(':')));
+    }
+}
@@ -0,0 +1,146 @@
+/// Cookie struct to store a cookie set by a `Set-Cookie` response header
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::cookie::Cookie;
+/// use std::str::FromStr;
+///
+/// let cookie = Cookie::from_str(
+///     "session=abc123; Domain=example.com; Path=/; Max-Age=3600; Secure; HttpOnly; SameSite=Lax",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(cookie.name, "session");
+/// assert_eq!(cookie.value, "abc123");
+/// assert_eq!(cookie.domain.as_deref(), Some("example.com"));
+/// assert_eq!(cookie.max_age, Some(3600));
+/// assert!(cookie.secure && cookie.http_only);
+/// assert_eq!(cookie.same_site.as_deref(), Some("Lax"));
+/// ```
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cookie {
+    /// Cookie name
+    pub name: String,
+    /// Cookie value
+    pub value: String,
+    /// Domain the cookie is sent to, `None` for the host of the response only
+    pub domain: Option<String>,
+    /// Path the cookie is sent to
+    pub path: Option<String>,
+    /// Expiry date of the `Expires` attribute
+    pub expires: Option<String>,
+    /// Lifetime in seconds of the `Max-Age` attribute, which takes precedence over `Expires`
+    pub max_age: Option<i64>,
+    /// Only sent over HTTPS
+    pub secure: bool,
+    /// Not accessible to JavaScript
+    pub http_only: bool,
+    /// Cross-site policy of the `SameSite` attribute, e.g. `Lax`
+    pub same_site: Option<String>,
+}
+
+impl Cookie {
+    /// Human readable expiry, `Session` when the cookie lasts until the browser closes
+    pub fn expiry(&self) -> String {
+        match (self.max_age, &self.expires) {
+            (Some(seconds), _) if seconds <= 0 => "Expired".to_string(),
+            (Some(seconds), _) => format!("{seconds}s"),
+            (None, Some(expires)) => expires.clone(),
+            (None, None) => "Session".to_string(),
+        }
+    }
+
+    /// Get the cookies of every `Set-Cookie` header, skipping the invalid ones
+    pub fn from_headers(headers: &[crate::network::Header]) -> Vec<Self> {
+        headers
+            .iter()
+            .filter(|header| header.key.eq_ignore_ascii_case("set-cookie"))
+            .filter_map(|header| header.value.parse().ok())
+            .collect()
+    }
+}
+
+impl std::str::FromStr for Cookie {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(';');
+        let (name, value) = parts
+            .next()
+            .and_then(|pair| pair.split_once('='))
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid cookie, please use name=value"))?;
+
+        let mut cookie = Cookie {
+            name: name.trim().to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            ..Default::default()
+        };
+
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim().to_string();
+            match key.trim().to_lowercase().as_str() {
+                "domain" => cookie.domain = Some(value.trim_start_matches('.').to_string()),
+                "path" => cookie.path = Some(value),
+                "expires" => cookie.expires = Some(value),
+                "max-age" => cookie.max_age = value.parse().ok(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "samesite" => cookie.same_site = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(cookie)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::network::Header;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_cookie_from_str() {
+        let cookie = Cookie::from_str(
+            "id=\"a=b\"; expires=Wed, 21 Oct 2026 07:28:00 GMT; domain=.example.com",
+        )
+        .unwrap();
+        assert_eq!(cookie.value, "a=b");
+        assert_eq!(cookie.domain.as_deref(), Some("example.com"));
+        assert_eq!(cookie.expiry(), "Wed, 21 Oct 2026 07:28:00 GMT");
+        assert!(!cookie.secure);
+
+        let cookie = Cookie::from_str("empty=; Max-Age=0").unwrap();
+        assert_eq!(cookie.value, "");
+        assert_eq!(cookie.expiry(), "Expired");
+        assert_eq!(Cookie::from_str("a=1").unwrap().expiry(), "Session");
+
+        assert!(Cookie::from_str("novalue").is_err());
+        assert!(Cookie::from_str("=value").is_err());
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let headers = vec![
+            Header::from_str("Set-Cookie: a=1; Path=/").unwrap(),
+            Header::from_str("Content-Type: text/plain").unwrap(),
+            Header::from_str("set-cookie: b=2; HttpOnly").unwrap(),
+            Header::from_str("Set-Cookie: invalid").unwrap(),
+        ];
+
+        let cookies = Cookie::from_headers(&headers);
+        let names = cookies
+            .iter()
+            .map(|cookie| cookie.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["a", "b"]);
+        assert!(cookies[1].http_only);
+    }
+}
@@ -0,0 +1,150 @@
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Number of days since 1970-01-01 of the civil date, based on
+/// <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Civil date of the number of days since 1970-01-01, based on
+/// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Format a Unix timestamp in the ISO 8601 basic format, e.g. `20150830T123600Z`
+pub fn format_basic(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    let seconds = timestamp.rem_euclid(86400);
+
+    format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Unix timestamp of the date and time in UTC
+fn timestamp(year: i64, month: u32, day: u32, time: &str) -> Option<i64> {
+    let mut parts = time.split(':').map(|part| part.parse::<i64>().ok());
+    let hour = parts.next()??;
+    let minute = parts.next()??;
+    let second = parts.next().unwrap_or(Some(0))?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+fn month(name: &str) -> Option<u32> {
+    MONTHS
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(name))
+        .map(|index| index as u32 + 1)
+}
+
+/// Parse a certificate date as reported by curl into a Unix timestamp, the formats
+/// `Jan  1 00:00:00 2025 GMT` and `2025-01-01 00:00:00 GMT` are supported
+pub fn parse_certificate_date(value: &str) -> Option<i64> {
+    let parts = value.split_whitespace().collect::<Vec<_>>();
+
+    match parts.as_slice() {
+        [month_name, day, time, year, ..] => timestamp(
+            year.parse().ok()?,
+            month(month_name)?,
+            day.parse().ok()?,
+            time,
+        ),
+        [date, time, ..] => {
+            let mut date = date.split('-');
+            timestamp(
+                date.next()?.parse().ok()?,
+                date.next()?.parse().ok()?,
+                date.next()?.parse().ok()?,
+                time,
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Current Unix timestamp
+pub fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2024, 2, 29), 19782);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        for (year, month, day) in [(1970, 1, 1), (2000, 3, 1), (2024, 2, 29), (1969, 12, 31)] {
+            assert_eq!(
+                civil_from_days(days_from_civil(year, month, day)),
+                (year, month, day)
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_basic() {
+        assert_eq!(format_basic(0), "19700101T000000Z");
+        assert_eq!(format_basic(1440938160), "20150830T123600Z");
+    }
+
+    #[test]
+    fn test_parse_certificate_date() {
+        let table = vec![
+            ("Jan  1 00:00:00 1970 GMT", Some(0)),
+            ("Feb 29 12:30:15 2024 GMT", Some(1709209815)),
+            ("2024-02-29 12:30:15 GMT", Some(1709209815)),
+            ("Foo  1 00:00:00 2024 GMT", None),
+            ("2024-13-01 00:00:00 GMT", None),
+            ("yesterday", None),
+        ];
+
+        for (value, expected) in table {
+            assert_eq!(parse_certificate_date(value), expected, "{value}");
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/// Percent-encode everything but the unreserved characters of RFC 3986
+pub fn percent_encode(value: &[u8], encode_slash: bool) -> String {
+    value
+        .iter()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (*byte as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Decode the percent-encoded bytes, invalid escapes are kept as they are
+pub fn percent_decode(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = bytes
+            .get(index + 1..index + 3)
+            .filter(|_| bytes[index] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    decoded
+}
+
+/// Lowercase hexadecimal representation of the bytes
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode(b"a b/c~", false), "a%20b/c~");
+        assert_eq!(percent_encode(b"a b/c~", true), "a%20b%2Fc~");
+        assert_eq!(percent_decode("a%20b%2f%zz"), b"a b/%zz");
+        assert_eq!(hex(&[0, 171, 255]), "00abff");
+    }
+}
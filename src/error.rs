@@ -0,0 +1,221 @@
+use crate::network::{Stat, TransferError};
+
+/// Result type of cetar
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Enum for the errors of cetar, the network errors keep the partial `Stat` of the failed transfer
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::network::{send_request, Config};
+/// use cetar::Error;
+///
+/// // A port that was just free, nothing listens on it any more
+/// let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+/// let port = listener.local_addr().unwrap().port();
+/// drop(listener);
+/// let conf = Config {
+///     url: format!("http://127.0.0.1:{port}").into(),
+///     ..Default::default()
+/// };
+///
+/// let error = send_request(&conf).unwrap_err();
+///
+/// assert!(matches!(error, Error::ConnectionRefused(_)));
+/// assert!(error.stat().is_some());
+/// ```
+///
+#[derive(Debug)]
+pub enum Error {
+    /// The host name or the proxy name could not be resolved
+    Dns(Box<TransferError>),
+    /// The connection to the host or the proxy was refused or could not be made
+    ConnectionRefused(Box<TransferError>),
+    /// The connection or the whole transfer took longer than allowed
+    Timeout(Box<TransferError>),
+    /// The TLS handshake or the verification of the peer certificate failed
+    Tls(Box<TransferError>),
+    /// Any other failure of the transfer
+    Transfer(Box<TransferError>),
+    /// Invalid arguments or configuration
+    InvalidInput(String),
+    /// Reading or writing a file failed
+    Io(std::io::Error),
+    /// Serializing the report failed
+    Serialization(String),
+}
+
+impl Error {
+    /// Get the partial `Stat` of a failed transfer, if applicable
+    pub fn stat(&self) -> Option<&Stat> {
+        match self {
+            Error::Dns(transfer)
+            | Error::ConnectionRefused(transfer)
+            | Error::Timeout(transfer)
+            | Error::Tls(transfer)
+            | Error::Transfer(transfer) => Some(&transfer.stat),
+            Error::InvalidInput(_) | Error::Io(_) | Error::Serialization(_) => None,
+        }
+    }
+}
+
+impl From<TransferError> for Error {
+    fn from(transfer: TransferError) -> Self {
+        let transfer = Box::new(transfer);
+        let source = &transfer.source;
+
+        if source.is_couldnt_resolve_host() || source.is_couldnt_resolve_proxy() {
+            Error::Dns(transfer)
+        } else if source.is_couldnt_connect() {
+            Error::ConnectionRefused(transfer)
+        } else if source.is_operation_timedout() {
+            Error::Timeout(transfer)
+        } else if source.is_ssl_connect_error()
+            || source.is_peer_failed_verification()
+            || source.is_ssl_certproblem()
+            || source.is_ssl_cipher()
+            || source.is_ssl_cacert()
+            || source.is_ssl_cacert_badfile()
+            || source.is_ssl_crl_badfile()
+            || source.is_ssl_issuer_error()
+            || source.is_ssl_engine_notfound()
+            || source.is_ssl_engine_setfailed()
+            || source.is_use_ssl_failed()
+            || source.code() == curl_sys::CURLE_SSL_PINNEDPUBKEYNOTMATCH
+        {
+            Error::Tls(transfer)
+        } else if is_invalid_input(source) || source.is_unsupported_protocol() {
+            Error::InvalidInput(transfer.source.to_string())
+        } else {
+            Error::Transfer(transfer)
+        }
+    }
+}
+
+/// Check whether curl rejected an option or the URL rather than failed a transfer
+fn is_invalid_input(source: &curl::Error) -> bool {
+    // `CURLE_NOT_BUILT_IN` is not exposed by curl-sys
+    const CURLE_NOT_BUILT_IN: curl_sys::CURLcode = 4;
+
+    source.is_url_malformed()
+        || source.is_bad_function_argument()
+        || source.is_unknown_option()
+        || source.code() == CURLE_NOT_BUILT_IN
+}
+
+impl From<curl::Error> for Error {
+    fn from(source: curl::Error) -> Self {
+        // Setting an option fails before any transfer, so there is no partial `Stat` to keep
+        if is_invalid_input(&source) {
+            return Error::InvalidInput(source.to_string());
+        }
+
+        let stat = Stat {
+            error: Some(source.to_string()),
+            ..Default::default()
+        };
+
+        TransferError { stat, source }.into()
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Serialization(error.to_string())
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Dns(transfer)
+            | Error::ConnectionRefused(transfer)
+            | Error::Timeout(transfer)
+            | Error::Tls(transfer)
+            | Error::Transfer(transfer) => write!(f, "{transfer}"),
+            Error::InvalidInput(message) => write!(f, "{message}"),
+            Error::Io(error) => write!(f, "{error}"),
+            Error::Serialization(message) => write!(f, "Cannot serialize the report: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Dns(transfer)
+            | Error::ConnectionRefused(transfer)
+            | Error::Timeout(transfer)
+            | Error::Tls(transfer)
+            | Error::Transfer(transfer) => Some(&transfer.source),
+            Error::InvalidInput(_) | Error::Serialization(_) => None,
+            Error::Io(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_curl_error() {
+        let table = vec![
+            (curl_sys::CURLE_COULDNT_RESOLVE_HOST, "dns"),
+            (curl_sys::CURLE_COULDNT_RESOLVE_PROXY, "dns"),
+            (curl_sys::CURLE_COULDNT_CONNECT, "refused"),
+            (curl_sys::CURLE_OPERATION_TIMEDOUT, "timeout"),
+            (curl_sys::CURLE_PEER_FAILED_VERIFICATION, "tls"),
+            (curl_sys::CURLE_SSL_CONNECT_ERROR, "tls"),
+            (curl_sys::CURLE_URL_MALFORMAT, "input"),
+            (curl_sys::CURLE_UNSUPPORTED_PROTOCOL, "input"),
+            (curl_sys::CURLE_BAD_FUNCTION_ARGUMENT, "input"),
+            (curl_sys::CURLE_UNKNOWN_OPTION, "input"),
+            (4, "input"), // CURLE_NOT_BUILT_IN
+            (curl_sys::CURLE_RECV_ERROR, "transfer"),
+        ];
+
+        for (code, expected) in table {
+            let kind = match Error::from(curl::Error::new(code)) {
+                Error::Dns(_) => "dns",
+                Error::ConnectionRefused(_) => "refused",
+                Error::Timeout(_) => "timeout",
+                Error::Tls(_) => "tls",
+                Error::Transfer(_) => "transfer",
+                Error::InvalidInput(_) => "input",
+                Error::Io(_) => "io",
+                Error::Serialization(_) => "serialization",
+            };
+            assert_eq!(kind, expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn test_stat() {
+        let error = Error::from(curl::Error::new(curl_sys::CURLE_OPERATION_TIMEDOUT));
+        assert!(error.stat().unwrap().error.is_some());
+
+        let error = Error::from(curl::Error::new(curl_sys::CURLE_UNKNOWN_OPTION));
+        assert!(error.stat().is_none());
+
+        let error = Error::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert!(error.stat().is_none());
+        assert!(matches!(error, Error::Io(_)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_serde_json_error() {
+        let error = Error::from(serde_json::from_str::<u8>("x").unwrap_err());
+        assert!(matches!(error, Error::Serialization(_)));
+        assert!(error.stat().is_none());
+    }
+}
@@ -7,9 +7,37 @@
 //! Cetar is CLI application not a library. But some of the elements can be used as a library.
 //!
 
+/// Assertion module, contains checks on the response status, headers and body.
+pub mod assertion;
+/// Auth module, contains the authentication methods and the redaction of credentials.
+pub mod auth;
+/// Body module, contains the sources of the request body and the multipart form parts.
+pub mod body;
+/// Budget module, contains latency budgets and their checks.
+pub mod budget;
 /// Color module, contains enum for color and macros to make color.
 pub mod color;
+/// Comparison module, contains runs of the same request with different settings.
+pub mod comparison;
+/// Cookie module, contains the cookies set by the responses.
+pub mod cookie;
+/// Date module, contains calendar helpers for certificate and header dates.
+mod date;
+/// DNS module, contains the overrides of host name resolution and connection targets.
+pub mod dns;
+/// Encoding module, contains the percent-encoding helpers.
+mod encoding;
+/// Error module, contains the errors of cetar.
+pub mod error;
 /// Network module, contains network related functions and structs.
 pub mod network;
 /// Output module, contains display and output handling related functions.
 pub mod output;
+/// SigV4 module, contains the AWS Signature Version 4 request signing.
+pub mod sigv4;
+/// Statistics module, contains latency statistics over repeated requests.
+pub mod statistics;
+/// TLS module, contains the negotiated TLS session and peer certificate details.
+pub mod tls;
+
+pub use error::{Error, Result};
@@ -1,19 +1,44 @@
 #![allow(dead_code)]
+use std::io::Read;
+use std::process::ExitCode;
+use std::time::Duration;
+
 use cetar::{make_color, print_error};
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
+
+/// Exit code when a latency budget is exceeded
+const EXIT_BUDGET_EXCEEDED: u8 = 3;
+/// Exit code when a response assertion fails
+const EXIT_ASSERTION_FAILED: u8 = 4;
+/// Exit code for invalid arguments, the same as clap uses for usage errors
+const EXIT_INVALID_INPUT: u8 = 2;
+/// Exit code when the host name could not be resolved
+const EXIT_DNS: u8 = 5;
+/// Exit code when the connection was refused or could not be made
+const EXIT_CONNECTION_REFUSED: u8 = 6;
+/// Exit code when the connection or the transfer timed out
+const EXIT_TIMEOUT: u8 = 7;
+/// Exit code when the TLS handshake or certificate verification failed
+const EXIT_TLS: u8 = 8;
+/// Exit code when reading or writing a file failed
+const EXIT_IO: u8 = 9;
+/// Exit code when serializing the report failed
+const EXIT_SERIALIZATION: u8 = 10;
 
 #[derive(Parser, Debug)]
 #[command(name = "cetar", about = "💥 CURL execution timing analyzer", version, long_about = None)]
+#[clap(group = clap::ArgGroup::new("http").multiple(false))]
+#[clap(group = clap::ArgGroup::new("ip").multiple(false))]
+#[clap(group = clap::ArgGroup::new("auth").multiple(false))]
 struct Args {
     url: String,
 
     #[clap(
         short = 'X',
         long,
-        default_value = "GET",
-        help = "Available methods: GET, HEAD, POST, PUT, DELETE, CONNECT, OPTIONS, TRACE, PATCH"
+        help = "Available methods: GET, HEAD, POST, PUT, DELETE, CONNECT, OPTIONS, TRACE, PATCH [default: GET, or POST with data or a form]"
     )]
-    method: String,
+    method: Option<String>,
 
     #[clap(
         short = 'H',
@@ -25,9 +50,39 @@ struct Args {
     #[clap(
         short,
         long,
-        help = "HTTP request data to send, example: -d 'key=value' -d @file.json -d '{\"key\": \"value\"}'"
+        help = "HTTP request data to send, @file and @- stream a file and stdin, repeated data is joined with &. Unlike curl the newlines of a file are kept, the same as --data-binary, example: -d 'key=value' -d @file.json -d '{\"key\": \"value\"}'"
+    )]
+    data: Vec<String>,
+
+    #[clap(
+        long,
+        help = "HTTP request data to send as it is, @file and @- stream a file and stdin, example: --data-binary @image.png"
+    )]
+    data_binary: Vec<String>,
+
+    #[clap(
+        long,
+        help = "HTTP request data to percent-encode, example: --data-urlencode 'q=a b' --data-urlencode name@file.txt"
+    )]
+    data_urlencode: Vec<String>,
+
+    #[clap(
+        long,
+        help = "JSON data to send, checked to parse and sent with JSON Content-Type and Accept headers, example: --json '{\"key\": \"value\"}'"
+    )]
+    json: Vec<String>,
+
+    /// Data arguments in the order they were given
+    #[clap(skip)]
+    request_data: Vec<cetar::body::Data>,
+
+    #[clap(
+        short = 'F',
+        long,
+        conflicts_with_all = ["data", "data_binary", "data_urlencode", "json"],
+        help = "Multipart form field to send, sent with POST unless -X is given, example: -F name=value -F 'file=@photo.png;type=image/png'"
     )]
-    data: Option<String>,
+    form: Vec<cetar::body::FormPart>,
 
     #[clap(short, long, help = "Write output to <file>")]
     output: Option<String>,
@@ -35,12 +90,25 @@ struct Args {
     #[clap(short = 'l', long = "location", help = "Follow HTTP 3xx redirects")]
     follow_redirects: bool,
 
+    #[clap(
+        long,
+        help = "Follow HTTP 3xx redirects and send the credentials to every host they point to"
+    )]
+    location_trusted: bool,
+
     #[clap(short, long, help = "Verbose output")]
     verbose: bool,
 
     #[clap(short = 'B', long, help = "Display response body")]
     display_response_body: bool,
 
+    #[clap(
+        long,
+        conflicts_with_all = ["output", "display_response_body", "expect_body_contains", "expect_json_path"],
+        help = "Count and time the response body without keeping it, for large downloads"
+    )]
+    discard_body: bool,
+
     #[clap(short = 'G', long, help = "Display response headers")]
     display_response_headers: bool,
 
@@ -50,50 +118,760 @@ struct Args {
         help = "Main output color, available colors: black, red, green, yellow, blue, magenta, cyan, white"
     )]
     color: String,
+
+    #[clap(
+        short = 'n',
+        long,
+        default_value = "1",
+        help = "Number of times to send the request, shows latency statistics when greater than 1"
+    )]
+    count: usize,
+
+    #[clap(
+        long,
+        default_value = "screen",
+        help = "Output format, available formats: screen, json, csv, ndjson. Reports are written to stdout or the report file"
+    )]
+    format: String,
+
+    #[clap(
+        long,
+        help = "Write the report of a json, csv or ndjson format to <file>"
+    )]
+    report_file: Option<String>,
+
+    #[clap(
+        long,
+        value_parser = cetar::budget::parse_duration,
+        help = "Fail when the total time exceeds the duration, example: --max-total 500ms"
+    )]
+    max_total: Option<Duration>,
+
+    #[clap(
+        long,
+        value_parser = cetar::budget::parse_duration,
+        help = "Fail when the time to first byte exceeds the duration, example: --max-ttfb 200ms"
+    )]
+    max_ttfb: Option<Duration>,
+
+    #[clap(
+        long = "max",
+        help = "Fail when a phase exceeds the duration, phases: dns, tcp, tls, server, transfer, total, ttfb, example: --max tls=50ms"
+    )]
+    budgets: Vec<cetar::budget::Budget>,
+
+    #[clap(
+        long,
+        help = "Fail when the response status code differs, example: --expect-status 200"
+    )]
+    expect_status: Option<i32>,
+
+    #[clap(
+        long,
+        help = "Fail when the response header is missing or does not contain the value, example: --expect-header 'content-type: application/json'"
+    )]
+    expect_header: Vec<cetar::network::Header>,
+
+    #[clap(long, help = "Fail when the response body does not contain the text")]
+    expect_body_contains: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Fail when the JSON response body does not match, example: --expect-json-path '$.ok == true'"
+    )]
+    expect_json_path: Vec<String>,
+
+    #[clap(
+        long,
+        value_parser = cetar::budget::parse_duration,
+        help = "Warn when a peer certificate expires within the duration, example: --warn-cert-expiry 30d"
+    )]
+    warn_cert_expiry: Option<Duration>,
+
+    #[clap(
+        long,
+        help = "Report the negotiated TLS version, cipher and ALPN protocol, read best-effort from curl's verbose output"
+    )]
+    tls_session: bool,
+
+    #[clap(
+        long,
+        value_parser = cetar::budget::parse_duration,
+        help = "Maximum time for the connection phase, example: --connect-timeout 5s"
+    )]
+    connect_timeout: Option<Duration>,
+
+    #[clap(
+        long,
+        value_parser = cetar::budget::parse_duration,
+        help = "Maximum time for the whole transfer, example: --max-time 30s"
+    )]
+    max_time: Option<Duration>,
+
+    #[clap(
+        short = 'x',
+        long,
+        help = "Use the proxy, example: -x http://proxy:3128, -x socks5h://proxy:1080. Defaults to the http_proxy, https_proxy and all_proxy environment variables"
+    )]
+    proxy: Option<String>,
+
+    #[clap(
+        short = 'U',
+        long,
+        help = "Proxy credentials, example: -U user:password"
+    )]
+    proxy_user: Option<String>,
+
+    #[clap(
+        long,
+        help = "Comma separated list of hosts to reach without the proxy, example: --noproxy localhost,.internal"
+    )]
+    noproxy: Option<String>,
+
+    #[clap(
+        short = 'p',
+        long,
+        help = "Tunnel plain HTTP through the proxy with CONNECT"
+    )]
+    proxytunnel: bool,
+
+    #[clap(
+        short = 'E',
+        long,
+        help = "Client certificate file for mutual TLS, PEM format"
+    )]
+    cert: Option<String>,
+
+    #[clap(long, help = "Private key file of the client certificate, PEM format")]
+    key: Option<String>,
+
+    #[clap(long, help = "Password of the private key")]
+    key_password: Option<String>,
+
+    #[clap(
+        long,
+        help = "CA certificate bundle to verify the peer with instead of the system one"
+    )]
+    cacert: Option<String>,
+
+    #[clap(long, help = "Directory of CA certificates to verify the peer with")]
+    capath: Option<String>,
+
+    #[clap(
+        short = 'k',
+        long,
+        help = "Skip the verification of the peer certificate and host name"
+    )]
+    insecure: bool,
+
+    #[clap(
+        long,
+        help = "Public key the peer certificate must have, a PEM or DER file or sha256// hashes, example: --pinnedpubkey sha256//YhKJKSzoTt2b5FP18fvpHo7fJYqQCjAa3HWY3tvRMwE="
+    )]
+    pinnedpubkey: Option<String>,
+
+    #[clap(
+        short = 'u',
+        long,
+        help = "Server credentials, example: -u user:password"
+    )]
+    user: Option<String>,
+
+    #[clap(
+        long,
+        group = "auth",
+        requires = "user",
+        help = "Use basic authentication with the credentials, the default"
+    )]
+    basic: bool,
+
+    #[clap(
+        long,
+        group = "auth",
+        requires = "user",
+        help = "Use digest authentication with the credentials"
+    )]
+    digest: bool,
+
+    #[clap(
+        long,
+        group = "auth",
+        requires = "user",
+        help = "Use NTLM authentication with the credentials"
+    )]
+    ntlm: bool,
+
+    #[clap(
+        long,
+        group = "auth",
+        requires = "user",
+        help = "Use the most secure authentication method the server offers"
+    )]
+    anyauth: bool,
+
+    #[clap(
+        long,
+        conflicts_with = "user",
+        help = "Send the token in an Authorization: Bearer header"
+    )]
+    bearer: Option<String>,
+
+    #[clap(long, help = "Read the credentials from ~/.netrc")]
+    netrc: bool,
+
+    #[clap(
+        long,
+        help = "Read the credentials from the netrc file instead of ~/.netrc"
+    )]
+    netrc_file: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with = "form",
+        help = "Sign the request with AWS Signature Version 4, example: --aws-sigv4 aws:us-east-1:execute-api"
+    )]
+    aws_sigv4: Option<cetar::sigv4::SigV4>,
+
+    #[clap(
+        long,
+        requires = "aws_sigv4",
+        help = "AWS access key ID to sign with, defaults to the AWS_ACCESS_KEY_ID environment variable"
+    )]
+    aws_access_key_id: Option<String>,
+
+    #[clap(
+        long,
+        requires = "aws_access_key_id",
+        help = "AWS secret access key to sign with, defaults to the AWS_SECRET_ACCESS_KEY environment variable"
+    )]
+    aws_secret_access_key: Option<String>,
+
+    #[clap(
+        long,
+        requires = "aws_access_key_id",
+        help = "AWS session token of temporary credentials, defaults to the AWS_SESSION_TOKEN environment variable"
+    )]
+    aws_session_token: Option<String>,
+
+    #[clap(
+        long,
+        requires_all = ["client_id", "client_secret"],
+        conflicts_with_all = ["bearer", "user"],
+        help = "Fetch a bearer token from the OAuth2 token endpoint with the client credentials grant first, timed separately"
+    )]
+    oauth2_token_url: Option<String>,
+
+    #[clap(long, requires = "oauth2_token_url", help = "OAuth2 client ID")]
+    client_id: Option<String>,
+
+    #[clap(long, requires = "oauth2_token_url", help = "OAuth2 client secret")]
+    client_secret: Option<String>,
+
+    #[clap(
+        long,
+        requires = "oauth2_token_url",
+        help = "Space separated OAuth2 scopes to request"
+    )]
+    scope: Option<String>,
+
+    #[clap(
+        short = 'b',
+        long,
+        help = "Cookies to send, or a file to read them from, example: -b 'theme=dark; lang=en', -b cookies.txt"
+    )]
+    cookie: Option<String>,
+
+    #[clap(
+        short = 'c',
+        long,
+        help = "Write the cookies to the file after the request"
+    )]
+    cookie_jar: Option<String>,
+
+    #[clap(
+        long,
+        help = "Use the addresses for the host and port instead of resolving it, example: --resolve example.com:443:10.0.0.1"
+    )]
+    resolve: Vec<cetar::dns::Resolve>,
+
+    #[clap(
+        long,
+        help = "Connect to another host and port instead, keeping the Host header and SNI, example: --connect-to example.com:443:lb-2.example.net:443"
+    )]
+    connect_to: Vec<cetar::dns::ConnectTo>,
+
+    #[clap(long = "http1.0", group = "http", help = "Use HTTP/1.0")]
+    http1_0: bool,
+
+    #[clap(long = "http1.1", group = "http", help = "Use HTTP/1.1")]
+    http1_1: bool,
+
+    #[clap(
+        long,
+        group = "http",
+        help = "Use HTTP/2, negotiated with ALPN over TLS or an upgrade over plain text"
+    )]
+    http2: bool,
+
+    #[clap(
+        long,
+        group = "http",
+        help = "Use HTTP/2 over plain text without an upgrade"
+    )]
+    http2_prior_knowledge: bool,
+
+    #[clap(
+        long,
+        group = "http",
+        help = "Use HTTP/3, requires libcurl built with HTTP/3 support"
+    )]
+    http3: bool,
+
+    #[clap(
+        long,
+        group = "http",
+        conflicts_with = "count",
+        help = "Send the request with HTTP/1.0, HTTP/1.1, HTTP/2 and HTTP/3 and compare the timings"
+    )]
+    compare_http_versions: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = ["count", "compare_http_versions", "compare_ip_families"],
+        help = "Resolve the host and send the request to each of its IPv4 and IPv6 addresses and compare the timings"
+    )]
+    all_ips: bool,
+
+    #[clap(
+        short = '4',
+        long,
+        group = "ip",
+        help = "Resolve the host to IPv4 addresses only"
+    )]
+    ipv4: bool,
+
+    #[clap(
+        short = '6',
+        long,
+        group = "ip",
+        help = "Resolve the host to IPv6 addresses only"
+    )]
+    ipv6: bool,
+
+    #[clap(
+        long,
+        group = "ip",
+        conflicts_with_all = ["count", "compare_http_versions"],
+        help = "Send the request over IPv4 and over IPv6 and compare the timings"
+    )]
+    compare_ip_families: bool,
+}
+
+impl Args {
+    fn http_version(&self) -> cetar::network::HttpVersion {
+        use cetar::network::HttpVersion;
+
+        match (
+            self.http1_0,
+            self.http1_1,
+            self.http2,
+            self.http2_prior_knowledge,
+            self.http3,
+        ) {
+            (true, ..) => HttpVersion::Http10,
+            (_, true, ..) => HttpVersion::Http11,
+            (_, _, true, ..) => HttpVersion::Http2,
+            (.., true, _) => HttpVersion::Http2PriorKnowledge,
+            (.., true) => HttpVersion::Http3,
+            _ => HttpVersion::Any,
+        }
+    }
+
+    fn auth_method(&self) -> cetar::auth::AuthMethod {
+        use cetar::auth::AuthMethod;
+
+        match (self.digest, self.ntlm, self.anyauth) {
+            (true, ..) => AuthMethod::Digest,
+            (_, true, _) => AuthMethod::Ntlm,
+            (.., true) => AuthMethod::Any,
+            _ => AuthMethod::Basic,
+        }
+    }
+
+    fn ip_family(&self) -> cetar::dns::IpFamily {
+        use cetar::dns::IpFamily;
+
+        match (self.ipv4, self.ipv6) {
+            (true, _) => IpFamily::V4,
+            (_, true) => IpFamily::V6,
+            _ => IpFamily::Any,
+        }
+    }
+}
+
+/// Convert a parse error of an argument value
+fn invalid_input(error: anyhow::Error) -> cetar::Error {
+    cetar::Error::InvalidInput(error.to_string())
 }
 
 impl TryFrom<Args> for cetar::network::Config<'_> {
-    type Error = anyhow::Error;
+    type Error = cetar::Error;
 
     fn try_from(cli: Args) -> Result<Self, Self::Error> {
-        let data = if let Some(d) = cli.data {
-            match d.starts_with('@') {
-                true => std::fs::read_to_string(&d[1..])?,
-                false => d,
+        let http_version = cli.http_version();
+        let ip_family = cli.ip_family();
+        let auth_method = cli.auth_method();
+        let aws_credentials = match (cli.aws_access_key_id, cli.aws_secret_access_key) {
+            (Some(access_key_id), Some(secret_access_key)) => Some(cetar::sigv4::Credentials {
+                access_key_id,
+                secret_access_key,
+                session_token: cli.aws_session_token,
+            }),
+            (Some(_), None) => {
+                return Err(cetar::Error::InvalidInput(
+                    "--aws-access-key-id requires --aws-secret-access-key".to_string(),
+                ))
+            }
+            _ => cetar::sigv4::Credentials::from_env(),
+        };
+        let oauth2 = match (cli.oauth2_token_url, cli.client_id, cli.client_secret) {
+            (Some(token_url), Some(client_id), Some(client_secret)) => Some(cetar::auth::OAuth2 {
+                token_url,
+                client_id,
+                client_secret,
+                scope: cli.scope,
+            }),
+            _ => None,
+        };
+        // Standard input can only be read once, so it is buffered when the body is sent again
+        // or has to be hashed for a SigV4 signature, only S3 accepts an unsigned payload
+        let resend = cli.count > 1
+            || cli.compare_http_versions
+            || cli.compare_ip_families
+            || cli.all_ips
+            || cli.follow_redirects
+            || cli.location_trusted
+            || cli
+                .aws_sigv4
+                .as_ref()
+                .is_some_and(|sigv4| sigv4.service != "s3");
+        let data = match cetar::body::Body::from_data(&cli.request_data)? {
+            Some(cetar::body::Body::Stdin) if resend => {
+                let mut buffer = vec![];
+                std::io::stdin().read_to_end(&mut buffer)?;
+                Some(cetar::body::Body::from(buffer))
             }
-            .into()
-        } else {
-            None
+            data => data,
+        };
+
+        let mut headers = cli.headers;
+        if !cli.json.is_empty() {
+            for key in ["Content-Type", "Accept"] {
+                if !headers
+                    .iter()
+                    .any(|header| header.key.eq_ignore_ascii_case(key))
+                {
+                    headers.push(cetar::network::Header {
+                        key: key.to_string(),
+                        value: "application/json".to_string(),
+                    });
+                }
+            }
+        }
+
+        // The body streams to the output file unless it is needed in memory afterwards
+        let format: cetar::output::Format =
+            cli.format.as_str().try_into().map_err(invalid_input)?;
+        if cli.report_file.is_some() && format == cetar::output::Format::Screen {
+            return Err(cetar::Error::InvalidInput(
+                "--report-file requires the json, csv or ndjson format".to_string(),
+            ));
+        }
+        let keep_body = cli.display_response_body
+            || !cli.expect_body_contains.is_empty()
+            || !cli.expect_json_path.is_empty();
+        let sink = match &cli.output {
+            _ if cli.discard_body => cetar::network::Sink::Discard,
+            Some(output) if !keep_body => cetar::network::Sink::File(output.clone().into()),
+            _ => cetar::network::Sink::Memory,
+        };
+
+        let mut budgets = cli.budgets;
+        if let Some(limit) = cli.max_total {
+            budgets.push(cetar::budget::Budget {
+                metric: cetar::budget::Metric::Phase(cetar::network::Phase::Total),
+                limit,
+            });
+        }
+        if let Some(limit) = cli.max_ttfb {
+            budgets.push(cetar::budget::Budget {
+                metric: cetar::budget::Metric::TimeToFirstByte,
+                limit,
+            });
+        }
+
+        let mut assertions = vec![];
+        if let Some(code) = cli.expect_status {
+            assertions.push(cetar::assertion::Assertion::Status(code));
+        }
+        for header in cli.expect_header {
+            assertions.push(cetar::assertion::Assertion::Header(header));
+        }
+        for text in cli.expect_body_contains {
+            assertions.push(cetar::assertion::Assertion::BodyContains(text));
+        }
+        #[cfg(feature = "serde")]
+        for expression in cli.expect_json_path {
+            let path = expression.parse().map_err(invalid_input)?;
+            assertions.push(cetar::assertion::Assertion::JsonPath(path));
+        }
+        #[cfg(not(feature = "serde"))]
+        if let Some(expression) = cli.expect_json_path.first() {
+            return Err(cetar::Error::InvalidInput(format!(
+                "--expect-json-path {expression} requires the `serde` feature"
+            )));
+        }
+
+        // An explicit method is kept, even a GET with data
+        let method = match cli.method.as_deref() {
+            Some(method) => method.try_into().map_err(invalid_input)?,
+            None if !cli.form.is_empty() || data.is_some() => cetar::network::Method::Post,
+            None => cetar::network::Method::Get,
         };
 
         Ok(Self {
             url: cli.url.into(),
-            request_headers: cli.headers,
-            request_body: data.map(|x| x.into()),
-            method: cli.method.as_str().try_into()?,
-            color: cli.color.as_str().try_into()?,
+            request_headers: headers,
+            request_body: data,
+            method,
+            color: cli.color.as_str().try_into().map_err(invalid_input)?,
             output: cli.output.map(|x| x.into()),
             display_response_body: cli.display_response_body,
             display_response_headers: cli.display_response_headers,
-            follow_redirects: cli.follow_redirects,
+            follow_redirects: cli.follow_redirects || cli.location_trusted,
             verbose: cli.verbose,
+            count: cli.count,
+            format,
+            budgets,
+            assertions,
+            warn_cert_expiry: cli.warn_cert_expiry,
+            http_version,
+            connect_timeout: cli.connect_timeout,
+            max_time: cli.max_time,
+            proxy: cli.proxy.map(|x| x.into()),
+            proxy_user: cli.proxy_user.map(|x| x.into()),
+            noproxy: cli.noproxy.map(|x| x.into()),
+            proxy_tunnel: cli.proxytunnel,
+            resolve: cli.resolve,
+            connect_to: cli.connect_to,
+            ip_family,
+            client_cert: cli.cert.map(|x| x.into()),
+            client_key: cli.key.map(|x| x.into()),
+            key_password: cli.key_password.map(|x| x.into()),
+            ca_cert: cli.cacert.map(|x| x.into()),
+            ca_path: cli.capath.map(|x| x.into()),
+            insecure: cli.insecure,
+            pinned_public_key: cli.pinnedpubkey.map(|x| x.into()),
+            user: cli.user.map(|x| x.into()),
+            auth_method,
+            bearer: cli.bearer.map(|x| x.into()),
+            netrc: cli.netrc,
+            netrc_file: cli.netrc_file.map(|x| x.into()),
+            aws_sigv4: cli.aws_sigv4,
+            aws_credentials,
+            oauth2,
+            cookie: cli.cookie.map(|x| x.into()),
+            cookie_jar: cli.cookie_jar.map(|x| x.into()),
+            form: cli.form,
+            sink,
+            location_trusted: cli.location_trusted,
+            tls_session: cli.tls_session,
+            report_file: cli.report_file.map(|x| x.into()),
         })
     }
 }
 
-fn execute() -> anyhow::Result<()> {
-    let parsed = Args::parse();
+/// Show the timings of a failed transfer as far as it got, and pass the error on
+fn display_partial(config: &cetar::network::Config, error: cetar::Error) -> cetar::Error {
+    let Some(stat) = error.stat() else {
+        return error;
+    };
+
+    let result = match config.format {
+        cetar::output::Format::Screen => {
+            cetar::output::Screen::new(config, stat).display();
+            Ok(())
+        }
+        _ => cetar::output::write_report(config, std::slice::from_ref(stat)),
+    };
+
+    match result {
+        Ok(()) => error,
+        Err(e) => e,
+    }
+}
+
+/// Map the error to its documented exit code
+fn exit_code(error: &cetar::Error) -> ExitCode {
+    match error {
+        cetar::Error::Dns(_) => ExitCode::from(EXIT_DNS),
+        cetar::Error::ConnectionRefused(_) => ExitCode::from(EXIT_CONNECTION_REFUSED),
+        cetar::Error::Timeout(_) => ExitCode::from(EXIT_TIMEOUT),
+        cetar::Error::Tls(_) => ExitCode::from(EXIT_TLS),
+        cetar::Error::Transfer(_) => ExitCode::FAILURE,
+        cetar::Error::InvalidInput(_) => ExitCode::from(EXIT_INVALID_INPUT),
+        cetar::Error::Io(_) => ExitCode::from(EXIT_IO),
+        cetar::Error::Serialization(_) => ExitCode::from(EXIT_SERIALIZATION),
+    }
+}
+
+/// Collect the data arguments of every kind in the order they were given, as curl joins them
+fn request_data(matches: &clap::ArgMatches) -> Vec<cetar::body::Data> {
+    let kind = |id: &str, value: String| match id {
+        "data_binary" => cetar::body::Data::Binary(value),
+        "data_urlencode" => cetar::body::Data::UrlEncode(value),
+        "json" => cetar::body::Data::Json(value),
+        _ => cetar::body::Data::Text(value),
+    };
+
+    let mut data = ["data", "data_binary", "data_urlencode", "json"]
+        .into_iter()
+        .flat_map(|id| {
+            let indices = matches.indices_of(id).into_iter().flatten();
+            let values = matches.get_many::<String>(id).into_iter().flatten();
+            indices.zip(values.map(move |value| kind(id, value.clone())))
+        })
+        .collect::<Vec<_>>();
+    data.sort_by_key(|(index, _)| *index);
+
+    data.into_iter().map(|(_, data)| data).collect()
+}
+
+fn execute() -> cetar::Result<ExitCode> {
+    let matches = Args::command().get_matches();
+    let mut parsed = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    parsed.request_data = request_data(&matches);
+    let compare_http_versions = parsed.compare_http_versions;
+    let compare_ip_families = parsed.compare_ip_families;
+    let all_ips = parsed.all_ips;
     let config = cetar::network::Config::try_from(parsed)?;
-    let result = cetar::network::send_request(&config)?;
 
-    cetar::output::handle_output(&config, &result)?;
-    cetar::output::Screen::new(&config, &result).display();
+    let mut comparison = match (compare_http_versions, compare_ip_families, all_ips) {
+        (true, ..) => cetar::comparison::http_versions(&config),
+        (_, true, _) => cetar::comparison::ip_families(&config),
+        (.., true) => cetar::comparison::ip_addresses(&config)?,
+        _ => vec![],
+    };
+    let mut errors = vec![];
+    let results = match comparison.is_empty() {
+        true => {
+            let mut results = vec![];
+            for (run, result) in cetar::network::send_requests(&config)
+                .into_iter()
+                .enumerate()
+            {
+                match result {
+                    Ok(stat) => results.push(stat),
+                    Err(e) => errors.push((run + 1, e)),
+                }
+            }
+            // Without any successful run the last failure is shown as far as it got
+            if results.is_empty() {
+                if let Some((_, e)) = errors.pop() {
+                    for (run, e) in &errors {
+                        print_error!("Run {} of {}: {}", run, config.count, e);
+                    }
+                    return Err(display_partial(&config, e));
+                }
+            }
+            results
+        }
+        false => cetar::comparison::stats(&mut comparison),
+    };
+    let Some(result) = results.last() else {
+        let error = comparison
+            .into_iter()
+            .find_map(|variant| variant.result.err());
+        return Err(
+            error.unwrap_or_else(|| cetar::Error::InvalidInput("No request was sent".to_string()))
+        );
+    };
+    let violations = cetar::budget::check(&config.budgets, &results);
+    let failures = cetar::assertion::check(&config.assertions, &results);
+
+    match config.format {
+        cetar::output::Format::Screen => {
+            cetar::output::handle_output(&config, result)?;
+
+            let screen = cetar::output::Screen::new(&config, result)
+                .with_violations(&violations)
+                .with_failures(&failures)
+                .with_comparison(&comparison);
+            if results.len() > 1 {
+                let statistics = cetar::statistics::Statistics::from(results.as_slice());
+                screen.with_statistics(&statistics).display();
+            } else {
+                screen.display();
+            }
+        }
+        _ => {
+            cetar::output::handle_output(&config, result)?;
+            cetar::output::write_report(&config, &results)?;
+            for variant in &comparison {
+                if let Err(e) = &variant.result {
+                    print_error!("{}: {}", variant.label, e);
+                }
+            }
+            if let (Some(threshold), Some(tls)) = (config.warn_cert_expiry, &result.tls) {
+                if tls.expires_within(threshold) {
+                    print_error!(
+                        "Warning: certificate expires in {} days",
+                        tls.expires_in_days().unwrap_or_default()
+                    );
+                }
+            }
+            for violation in &violations {
+                print_error!(
+                    "Budget exceeded: {} {}ms > {}ms",
+                    violation.budget.metric.name(),
+                    violation.actual.as_millis(),
+                    violation.budget.limit.as_millis()
+                );
+            }
+            for failure in &failures {
+                print_error!(
+                    "Assertion failed: {} got {}",
+                    failure.assertion,
+                    failure.actual
+                );
+            }
+        }
+    }
+
+    for (run, e) in &errors {
+        print_error!("Run {} of {}: {}", run, config.count, e);
+    }
+    if let Some((_, e)) = errors.first() {
+        return Ok(exit_code(e));
+    }
+
+    if !failures.is_empty() {
+        return Ok(ExitCode::from(EXIT_ASSERTION_FAILED));
+    }
+
+    if !violations.is_empty() {
+        return Ok(ExitCode::from(EXIT_BUDGET_EXCEEDED));
+    }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-fn main() {
-    if let Err(e) = execute() {
-        print_error!("Error: {}", e);
+fn main() -> ExitCode {
+    match execute() {
+        Ok(code) => code,
+        Err(e) => {
+            print_error!("Error: {}", e);
+            exit_code(&e)
+        }
     }
 }
@@ -1,12 +1,70 @@
 use std::io::Write;
 use std::time::Duration;
 
+use crate::assertion::Failure;
+use crate::budget::{Metric, Violation};
+use crate::color::Color;
+use crate::comparison::Variant;
+use crate::cookie::Cookie;
+use crate::make_color;
+#[cfg(feature = "serde")]
+use crate::network::duration_ms;
 use crate::network::Config;
-use crate::network::Stat;
+use crate::network::{Phase, Sink, Stat};
+use crate::statistics::Statistics;
+use crate::tls::Tls;
+
+/// Enum for the output formats
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::output::Format;
+///
+/// let format = Format::try_from("screen").unwrap();
+///
+/// assert_eq!(format, Format::Screen);
+/// ```
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum Format {
+    /// Human readable screen output
+    #[default]
+    Screen,
+    /// JSON document of the `Stat`, requires the `serde` feature
+    #[cfg(feature = "serde")]
+    Json,
+    /// CSV with a header and one row per request
+    Csv,
+    /// Newline delimited JSON with one line per request, requires the `serde` feature
+    #[cfg(feature = "serde")]
+    Ndjson,
+}
+
+impl TryFrom<&str> for Format {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "screen" => Ok(Self::Screen),
+            "csv" => Ok(Self::Csv),
+            #[cfg(feature = "serde")]
+            "json" => Ok(Self::Json),
+            #[cfg(feature = "serde")]
+            "ndjson" => Ok(Self::Ndjson),
+            #[cfg(not(feature = "serde"))]
+            "json" | "ndjson" => Err(anyhow::anyhow!("JSON output requires the `serde` feature")),
+            _ => Err(anyhow::anyhow!(
+                "Invalid format, must be one of: screen, json, csv, ndjson"
+            )),
+        }
+    }
+}
 
 struct NetworkEvent<'a> {
     name: &'a str,
     duration: Duration,
+    metric: Option<Metric>,
 }
 
 impl<'a> NetworkEvent<'a> {
@@ -14,6 +72,7 @@ impl<'a> NetworkEvent<'a> {
         stat.dns_lookup().map(|duration| Self {
             name: "DNS Lookup",
             duration,
+            metric: Some(Metric::Phase(Phase::DnsLookup)),
         })
     }
 
@@ -21,6 +80,31 @@ impl<'a> NetworkEvent<'a> {
         stat.tcp_handshake().map(|duration| Self {
             name: "TCP Handshake",
             duration,
+            metric: Some(Metric::Phase(Phase::TcpHandshake)),
+        })
+    }
+
+    fn proxy_connect(stat: &Stat) -> Option<Self> {
+        stat.proxy_connect().map(|duration| Self {
+            name: "Proxy Connect",
+            duration,
+            metric: Some(Metric::Phase(Phase::TcpHandshake)),
+        })
+    }
+
+    fn token_fetch(stat: &Stat) -> Option<Self> {
+        stat.token_fetch.map(|duration| Self {
+            name: "Token Fetch",
+            duration,
+            metric: None,
+        })
+    }
+
+    fn tunnel_establishment(stat: &Stat) -> Option<Self> {
+        stat.tunnel_establishment().map(|duration| Self {
+            name: "Tunnel Establishment",
+            duration,
+            metric: None,
         })
     }
 
@@ -28,6 +112,7 @@ impl<'a> NetworkEvent<'a> {
         stat.tls_handshake().map(|duration| Self {
             name: "TLS Handshake",
             duration,
+            metric: Some(Metric::Phase(Phase::TlsHandshake)),
         })
     }
 
@@ -35,6 +120,7 @@ impl<'a> NetworkEvent<'a> {
         stat.server_processing().map(|duration| Self {
             name: "Server Processing",
             duration,
+            metric: Some(Metric::Phase(Phase::ServerProcessing)),
         })
     }
 
@@ -42,6 +128,7 @@ impl<'a> NetworkEvent<'a> {
         stat.content_transfer().map(|duration| Self {
             name: "Content Transfer",
             duration,
+            metric: Some(Metric::Phase(Phase::ContentTransfer)),
         })
     }
 
@@ -49,6 +136,7 @@ impl<'a> NetworkEvent<'a> {
         Some(Self {
             name: "Total",
             duration: stat.total,
+            metric: Some(Metric::Phase(Phase::Total)),
         })
     }
 
@@ -56,6 +144,7 @@ impl<'a> NetworkEvent<'a> {
         Some(Self {
             name: "Name Lookup",
             duration: stat.name_lookup,
+            metric: None,
         })
     }
 
@@ -63,6 +152,7 @@ impl<'a> NetworkEvent<'a> {
         Some(Self {
             name: "Connect",
             duration: stat.connect,
+            metric: None,
         })
     }
 
@@ -70,6 +160,7 @@ impl<'a> NetworkEvent<'a> {
         stat.tls_handshake().map(|_| Self {
             name: "App Connect",
             duration: stat.app_connect,
+            metric: None,
         })
     }
 
@@ -77,6 +168,7 @@ impl<'a> NetworkEvent<'a> {
         Some(Self {
             name: "Pre Transfer",
             duration: stat.pre_transfer,
+            metric: None,
         })
     }
 
@@ -84,6 +176,7 @@ impl<'a> NetworkEvent<'a> {
         Some(Self {
             name: "Start Transfer",
             duration: stat.start_transfer,
+            metric: Some(Metric::TimeToFirstByte),
         })
     }
 }
@@ -103,17 +196,146 @@ impl<'a> NetworkEvent<'a> {
 /// let screen = Screen::new(&config, &stat);
 /// screen.display();
 /// ```
+///
+/// Statistics of repeated requests replace the single-run timings:
+///
+/// ```rust
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::Screen;
+/// use cetar::statistics::Statistics;
+///
+/// let config = Config::default();
+/// let stats = vec![Stat::default(), Stat::default()];
+/// let statistics = Statistics::from(stats.as_slice());
+///
+/// let screen = Screen::new(&config, &stats[1]).with_statistics(&statistics);
+/// screen.display();
+/// ```
+///
+/// Exceeded latency budgets are highlighted:
+///
+/// ```rust
+/// use cetar::budget::{check, Budget};
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::Screen;
+/// use std::str::FromStr;
+/// use std::time::Duration;
+///
+/// let config = Config {
+///     budgets: vec![Budget::from_str("total=100ms").unwrap()],
+///     ..Default::default()
+/// };
+/// let stats = vec![Stat {
+///     total: Duration::from_millis(150),
+///     ..Default::default()
+/// }];
+/// let violations = check(&config.budgets, &stats);
+///
+/// let screen = Screen::new(&config, &stats[0]).with_violations(&violations);
+/// screen.display();
+/// ```
+///
+/// Failed assertions are listed in their own section:
+///
+/// ```rust
+/// use cetar::assertion::{check, Assertion};
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::Screen;
+///
+/// let config = Config {
+///     assertions: vec![Assertion::Status(200)],
+///     ..Default::default()
+/// };
+/// let stats = vec![Stat {
+///     response_status_code: Some(500),
+///     ..Default::default()
+/// }];
+/// let failures = check(&config.assertions, &stats);
+///
+/// let screen = Screen::new(&config, &stats[0]).with_failures(&failures);
+/// screen.display();
+/// ```
 pub struct Screen<'a> {
     config: &'a Config<'a>,
     stat: &'a Stat,
+    statistics: Option<&'a Statistics>,
+    violations: &'a [Violation],
+    failures: &'a [Failure],
+    comparison: &'a [Variant],
 }
 
 impl<'a> Screen<'a> {
     const PADDING: usize = 35;
     const MAX_PADDING: usize = 50;
+    const COLUMN_WIDTH: usize = 10;
 
     pub fn new(config: &'a Config<'a>, stat: &'a Stat) -> Self {
-        Self { config, stat }
+        Self {
+            config,
+            stat,
+            statistics: None,
+            violations: &[],
+            failures: &[],
+            comparison: &[],
+        }
+    }
+
+    /// Display the statistics table instead of the single-run timings.
+    pub fn with_statistics(mut self, statistics: &'a Statistics) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
+
+    /// Highlight the exceeded latency budgets.
+    pub fn with_violations(mut self, violations: &'a [Violation]) -> Self {
+        self.violations = violations;
+        self
+    }
+
+    /// List the failed assertions.
+    pub fn with_failures(mut self, failures: &'a [Failure]) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    /// Display the phases of the compared variants side by side instead of the single-run timings.
+    /// Violations and failures refer to the successful variants, in order.
+    pub fn with_comparison(mut self, comparison: &'a [Variant]) -> Self {
+        self.comparison = comparison;
+        self
+    }
+
+    /// Describe the run a violation or failure happened in, empty for a single run
+    fn run_label(&self, run: usize) -> String {
+        if !self.comparison.is_empty() {
+            return self
+                .comparison
+                .iter()
+                .filter(|variant| variant.result.is_ok())
+                .nth(run)
+                .map(|variant| format!(" ({})", variant.label))
+                .unwrap_or_default();
+        }
+
+        match self.statistics {
+            Some(_) => format!(" (request #{})", run + 1),
+            None => String::new(),
+        }
+    }
+
+    fn violation(&self, metric: Option<Metric>) -> Option<&Violation> {
+        metric.and_then(|metric| {
+            self.violations
+                .iter()
+                .find(|violation| violation.budget.metric == metric)
+        })
+    }
+
+    fn paint_name(&self, name: &str, metric: Option<Metric>) -> String {
+        match self.violation(metric) {
+            Some(_) => make_color!(Color::Red as u8, name),
+            None => self.config.color.paint(name),
+        }
     }
 
     #[inline]
@@ -136,9 +358,17 @@ impl<'a> Screen<'a> {
 
     fn display_events(&self, events: &[Option<NetworkEvent>]) {
         for event in events.iter().flatten() {
+            let budget = self
+                .violation(event.metric)
+                .map(|violation| {
+                    let limit = format!(" > {}ms", violation.budget.limit.as_millis());
+                    make_color!(Color::Red as u8, limit)
+                })
+                .unwrap_or_default();
+
             println!(
-                "{name:<width$} {bar} {duration_ms}ms",
-                name = self.config.color.paint(event.name),
+                "{name:<width$} {bar} {duration_ms}ms{budget}",
+                name = self.paint_name(event.name, event.metric),
                 duration_ms = event.duration.as_millis(),
                 bar = self.event_bar(event),
                 width = Self::PADDING
@@ -149,9 +379,15 @@ impl<'a> Screen<'a> {
     fn display_network_timings(&self) {
         println!("Network Timings:");
 
+        let connect = match self.stat.via_proxy.unwrap_or_default() {
+            true => NetworkEvent::proxy_connect(self.stat),
+            false => NetworkEvent::tcp_handshake(self.stat),
+        };
         let events = &[
+            NetworkEvent::token_fetch(self.stat),
             NetworkEvent::dns_lookup(self.stat),
-            NetworkEvent::tcp_handshake(self.stat),
+            connect,
+            NetworkEvent::tunnel_establishment(self.stat),
             NetworkEvent::tls_handshake(self.stat),
             NetworkEvent::server_processing(self.stat),
             NetworkEvent::content_transfer(self.stat),
@@ -175,6 +411,339 @@ impl<'a> Screen<'a> {
         self.display_events(events);
     }
 
+    fn format_millis(duration: Option<Duration>) -> String {
+        let value = duration
+            .map(|d| format!("{:.2}ms", d.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "-".to_string());
+        format!("{value:>width$}", width = Self::COLUMN_WIDTH)
+    }
+
+    fn phase_columns() -> String {
+        ["DNS", "TCP", "TLS", "Server", "Transfer", "Total"]
+            .iter()
+            .map(|column| format!("{column:>width$}", width = Self::COLUMN_WIDTH))
+            .collect()
+    }
+
+    fn phase_values(stat: &Stat) -> String {
+        Phase::ALL
+            .iter()
+            .map(|phase| Self::format_millis(stat.phase(*phase)))
+            .collect()
+    }
+
+    fn display_redirects(&self) {
+        println!(
+            "Redirects ({} redirects, {}ms):",
+            self.stat.redirect_count,
+            (self.stat.redirect_time + self.stat.total).as_millis()
+        );
+        println!(
+            "{:<width$}{}",
+            "",
+            Self::phase_columns(),
+            width = Self::PADDING
+        );
+
+        let hops = self.stat.redirects.iter().chain(std::iter::once(self.stat));
+        for (index, hop) in hops.enumerate() {
+            let name = format!(
+                "{}. {}",
+                index + 1,
+                hop.response_status_code
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "Unknown".to_string())
+            );
+            println!(
+                "{name}{values}  {url}",
+                name = self
+                    .config
+                    .color
+                    .paint(&format!("{name:<width$}", width = Self::PADDING)),
+                values = Self::phase_values(hop),
+                url = hop.effective_url.as_deref().unwrap_or_default()
+            );
+        }
+    }
+
+    fn display_field(&self, name: &str, value: &str) {
+        println!(
+            "{name}{value}",
+            name = self
+                .config
+                .color
+                .paint(&format!("{name:<width$}", width = Self::PADDING)),
+        );
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+        let mut value = bytes as f64;
+        let mut unit = "B";
+        for next in UNITS {
+            if value < 1024.0 {
+                break;
+            }
+            value /= 1024.0;
+            unit = next;
+        }
+
+        match unit {
+            "B" => format!("{bytes} B"),
+            _ => format!("{value:.2} {unit}"),
+        }
+    }
+
+    fn display_transfer(&self) {
+        println!("Transfer:");
+
+        let address = |ip: &Option<String>, port: Option<u16>| match (ip, port) {
+            (Some(ip), Some(port)) => format!("{ip}:{port}"),
+            (Some(ip), None) => ip.to_string(),
+            _ => "-".to_string(),
+        };
+        let fields = [
+            (
+                "Downloaded",
+                format!(
+                    "{} ({}/s)",
+                    Self::format_bytes(self.stat.download_size),
+                    Self::format_bytes(self.stat.download_speed as u64)
+                ),
+            ),
+            (
+                "Uploaded",
+                format!(
+                    "{} ({}/s)",
+                    Self::format_bytes(self.stat.upload_size),
+                    Self::format_bytes(self.stat.upload_speed as u64)
+                ),
+            ),
+            ("Header Size", Self::format_bytes(self.stat.header_size)),
+            ("Request Size", Self::format_bytes(self.stat.request_size)),
+            ("New Connections", self.stat.num_connects.to_string()),
+            (
+                "Remote Address",
+                address(&self.stat.ip_address, self.stat.primary_port),
+            ),
+            (
+                "Local Address",
+                address(&self.stat.local_ip, self.stat.local_port),
+            ),
+            (
+                "Content Type",
+                self.stat.content_type.clone().unwrap_or("-".to_string()),
+            ),
+        ];
+
+        for (name, value) in fields {
+            self.display_field(name, &value);
+        }
+    }
+
+    fn display_tls(&self, tls: &Tls) {
+        let warn = self
+            .config
+            .warn_cert_expiry
+            .is_some_and(|threshold| tls.expires_within(threshold));
+
+        println!("TLS:");
+        let session = [
+            ("Version", &tls.version),
+            ("Cipher", &tls.cipher),
+            ("ALPN", &tls.alpn),
+        ];
+        // The session details are only collected when requested or in verbose mode
+        if self.config.tls_session || session.iter().any(|(_, value)| value.is_some()) {
+            for (name, value) in session {
+                self.display_field(name, value.as_deref().unwrap_or("-"));
+            }
+        }
+
+        for (index, certificate) in tls.certificates.iter().enumerate() {
+            println!();
+            println!("Certificate #{}:", index + 1);
+
+            let expiry = match certificate.expires_in_days {
+                Some(days) if days < 0 => format!("expired {} days ago", -days),
+                Some(days) => format!("{days} days"),
+                None => "-".to_string(),
+            };
+            let fields = [
+                ("Subject", certificate.subject.clone()),
+                ("Issuer", certificate.issuer.clone()),
+                (
+                    "Subject Alternative Names",
+                    Some(certificate.subject_alt_names.join(", ")).filter(|san| !san.is_empty()),
+                ),
+                ("Not Before", certificate.not_before.clone()),
+                ("Not After", certificate.not_after.clone()),
+                ("Expires In", Some(expiry)),
+            ];
+            for (name, value) in fields {
+                self.display_field(name, value.as_deref().unwrap_or("-"));
+            }
+        }
+
+        if warn {
+            println!();
+            println!(
+                "{}",
+                make_color!(
+                    Color::Yellow as u8,
+                    format!(
+                        "Warning: certificate expires in {} days",
+                        tls.expires_in_days().unwrap_or_default()
+                    )
+                )
+            );
+        }
+    }
+
+    fn display_cookies(&self, cookies: &[&Cookie]) {
+        println!("Cookies:");
+
+        for cookie in cookies {
+            let mut attributes = vec![
+                format!("Value={}", crate::auth::REDACTED),
+                format!("Domain={}", cookie.domain.as_deref().unwrap_or("-")),
+                format!("Path={}", cookie.path.as_deref().unwrap_or("-")),
+                format!("Expires={}", cookie.expiry()),
+            ];
+            if cookie.secure {
+                attributes.push("Secure".to_string());
+            }
+            if cookie.http_only {
+                attributes.push("HttpOnly".to_string());
+            }
+            if let Some(same_site) = &cookie.same_site {
+                attributes.push(format!("SameSite={same_site}"));
+            }
+
+            self.display_field(&cookie.name, &attributes.join("; "));
+        }
+    }
+
+    fn display_comparison(&self) {
+        println!("Comparison:");
+        println!(
+            "{:<width$}{}",
+            "",
+            Self::phase_columns(),
+            width = Self::PADDING
+        );
+
+        for variant in self.comparison {
+            let name = self.config.color.paint(&format!(
+                "{:<width$}",
+                variant.label,
+                width = Self::PADDING
+            ));
+
+            match &variant.result {
+                Ok(stat) => println!(
+                    "{name}{values}  HTTP/{version} {status}",
+                    values = Self::phase_values(stat),
+                    version = stat.http_version.as_deref().unwrap_or("Unknown"),
+                    status = stat.response_status_code.unwrap_or_default()
+                ),
+                Err(e) => println!("{name}{}", make_color!(Color::Red as u8, e)),
+            }
+        }
+
+        if let [first, second] = self.comparison {
+            if let (Ok(base), Ok(other)) = (&first.result, &second.result) {
+                let name = format!("{} - {}", second.label, first.label);
+                println!(
+                    "{name:<width$}{}",
+                    Self::phase_deltas(base, other),
+                    width = Self::PADDING
+                );
+            }
+        }
+    }
+
+    /// Signed difference of each phase, red when slower and green when faster than the base
+    fn phase_deltas(base: &Stat, other: &Stat) -> String {
+        Phase::ALL
+            .iter()
+            .map(|phase| match (base.phase(*phase), other.phase(*phase)) {
+                (Some(base), Some(other)) => {
+                    let delta = (other.as_secs_f64() - base.as_secs_f64()) * 1000.0;
+                    let value = format!("{:+.2}ms", delta);
+                    let value = format!("{value:>width$}", width = Self::COLUMN_WIDTH);
+                    match delta > 0.0 {
+                        true => make_color!(Color::Red as u8, value),
+                        false => make_color!(Color::Green as u8, value),
+                    }
+                }
+                _ => Self::format_millis(None),
+            })
+            .collect()
+    }
+
+    fn display_statistics(&self, statistics: &Statistics) {
+        println!("Statistics ({} requests):", statistics.count);
+
+        let columns = ["Min", "Max", "Mean", "StdDev", "p50", "p90", "p95", "p99"]
+            .iter()
+            .map(|column| format!("{column:>width$}", width = Self::COLUMN_WIDTH))
+            .collect::<String>();
+        println!("{:<width$}{columns}", "", width = Self::PADDING);
+
+        for (phase, summary) in &statistics.phases {
+            let values = [
+                summary.min,
+                summary.max,
+                summary.mean,
+                summary.stddev,
+                summary.p50,
+                summary.p90,
+                summary.p95,
+                summary.p99,
+            ]
+            .iter()
+            .map(|duration| Self::format_millis(Some(*duration)))
+            .collect::<String>();
+
+            let name = format!("{:<width$}", phase.name(), width = Self::PADDING);
+            let name = self.paint_name(&name, Some(Metric::Phase(*phase)));
+            println!("{name}{values}");
+        }
+    }
+
+    fn display_violations(&self) {
+        println!("{}", make_color!(Color::Red as u8, "Budget Exceeded:"));
+
+        for violation in self.violations {
+            let run = self.run_label(violation.run);
+
+            println!(
+                "{name:<width$} {actual}ms > {limit}ms{run}",
+                name = make_color!(Color::Red as u8, violation.budget.metric.name()),
+                actual = violation.actual.as_millis(),
+                limit = violation.budget.limit.as_millis(),
+                width = Self::PADDING
+            );
+        }
+    }
+
+    fn display_failures(&self) {
+        println!("{}", make_color!(Color::Red as u8, "Assertions Failed:"));
+
+        for failure in self.failures {
+            let run = self.run_label(failure.run);
+
+            println!(
+                "{name:<width$} got {actual}{run}",
+                name = make_color!(Color::Red as u8, failure.assertion.to_string()),
+                actual = failure.actual,
+                width = Self::PADDING
+            );
+        }
+    }
+
     fn display_response_headers(&self) {
         println!();
         println!(
@@ -194,19 +763,16 @@ impl<'a> Screen<'a> {
             .max()
             .unwrap_or(Self::PADDING);
 
-        let width = if max_name_len > Self::MAX_PADDING {
-            Self::MAX_PADDING
-        } else if max_name_len < Self::PADDING {
-            Self::PADDING
-        } else {
-            max_name_len
-        };
+        let width = max_name_len.clamp(Self::PADDING, Self::MAX_PADDING);
 
         for header in &self.stat.response_headers {
+            let value = match crate::auth::is_sensitive(&header.key) {
+                true => crate::auth::redact_header(&header.key, &header.value),
+                false => header.value.clone(),
+            };
             println!(
                 "{key:<width$} {value:<width$}",
                 key = self.config.color.paint(header.header_key().as_str()),
-                value = header.value,
                 width = width
             );
         }
@@ -234,9 +800,46 @@ impl<'a> Screen<'a> {
             )
         );
         println!();
-        self.display_network_timings();
-        println!();
-        self.display_detailed_timings();
+        if !self.comparison.is_empty() {
+            self.display_comparison();
+        } else {
+            if let Some(statistics) = self.statistics {
+                self.display_statistics(statistics);
+            } else {
+                self.display_network_timings();
+                println!();
+                self.display_detailed_timings();
+            }
+            if !self.stat.redirects.is_empty() {
+                println!();
+                self.display_redirects();
+            }
+            println!();
+            self.display_transfer();
+            if let Some(tls) = &self.stat.tls {
+                println!();
+                self.display_tls(tls);
+            }
+            let cookies = self
+                .stat
+                .redirects
+                .iter()
+                .chain(std::iter::once(self.stat))
+                .flat_map(|hop| &hop.cookies)
+                .collect::<Vec<_>>();
+            if !cookies.is_empty() {
+                println!();
+                self.display_cookies(&cookies);
+            }
+        }
+        if !self.violations.is_empty() {
+            println!();
+            self.display_violations();
+        }
+        if !self.failures.is_empty() {
+            println!();
+            self.display_failures();
+        }
         if self.config.display_response_headers {
             println!();
             self.display_response_headers();
@@ -248,7 +851,8 @@ impl<'a> Screen<'a> {
     }
 }
 
-/// Handle the output of the request.
+/// Handle the output of the request, the response body kept in memory is written to
+/// `Config::output`. The report of other formats goes to `Config::report_file`, see `write_report`.
 ///
 /// # Example
 ///
@@ -262,13 +866,265 @@ impl<'a> Screen<'a> {
 /// handle_output(&config, &stat).unwrap();
 ///
 /// ```
-pub fn handle_output(config: &Config, stat: &Stat) -> anyhow::Result<()> {
-    if let Some(output) = &config.output {
-        if let Some(body) = stat.utf8_response_body() {
-            let mut file = std::fs::File::create(output.as_ref())?;
-            file.write_all(body.as_bytes())?;
+pub fn handle_output(config: &Config, stat: &Stat) -> crate::Result<()> {
+    // Other sinks wrote or discarded the body as it was received
+    if let (Some(output), Sink::Memory) = (&config.output, &config.sink) {
+        let mut file = std::fs::File::create(output.as_ref())?;
+        file.write_all(&stat.response_body)?;
+    }
+
+    Ok(())
+}
+
+/// JSON document of a single request
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct Report<'a> {
+    url: &'a str,
+    method: &'a crate::network::Method,
+    #[serde(flatten)]
+    stat: &'a Stat,
+    phases: std::collections::BTreeMap<crate::network::Phase, Option<f64>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> Report<'a> {
+    fn new(config: &'a Config<'a>, stat: &'a Stat) -> Self {
+        let phases = crate::network::Phase::ALL
+            .iter()
+            .map(|phase| {
+                let millis = stat.phase(*phase).map(|d| duration_ms::millis(&d));
+                (*phase, millis)
+            })
+            .collect();
+
+        Self {
+            url: &config.url,
+            method: &config.method,
+            stat,
+            phases,
         }
     }
+}
+
+/// Serialize the results as a JSON array with one object per request, also for a single
+/// request so the document always has the same shape. Timings are in milliseconds.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::to_json;
+///
+/// let config = Config::default();
+/// let stats = vec![Stat::default()];
+///
+/// let json = to_json(&config, &stats).unwrap();
+///
+/// assert!(json.starts_with('['));
+/// assert!(json.contains("\"phases\""));
+/// ```
+#[cfg(feature = "serde")]
+pub fn to_json(config: &Config, stats: &[Stat]) -> crate::Result<String> {
+    let reports = stats
+        .iter()
+        .map(|stat| Report::new(config, stat))
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(&reports)?)
+}
+
+/// Serialize the results as newline delimited JSON, one compact line per request.
+/// Timings are in milliseconds.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::to_ndjson;
+///
+/// let config = Config::default();
+/// let stats = vec![Stat::default(), Stat::default()];
+///
+/// let ndjson = to_ndjson(&config, &stats).unwrap();
+///
+/// assert_eq!(ndjson.lines().count(), 2);
+/// ```
+#[cfg(feature = "serde")]
+pub fn to_ndjson(config: &Config, stats: &[Stat]) -> crate::Result<String> {
+    let mut ndjson = String::new();
+    for stat in stats {
+        ndjson.push_str(&serde_json::to_string(&Report::new(config, stat))?);
+        ndjson.push('\n');
+    }
+
+    Ok(ndjson)
+}
+
+/// Columns of the CSV output, in order. Timings are in milliseconds.
+pub const CSV_COLUMNS: [&str; 36] = [
+    "run",
+    "url",
+    "method",
+    "ip_address",
+    "http_version",
+    "response_status_code",
+    "name_lookup",
+    "connect",
+    "app_connect",
+    "pre_transfer",
+    "start_transfer",
+    "total",
+    "dns_lookup",
+    "tcp_handshake",
+    "tls_handshake",
+    "server_processing",
+    "content_transfer",
+    "effective_url",
+    "redirect_count",
+    "redirect_time",
+    "download_size",
+    "upload_size",
+    "header_size",
+    "request_size",
+    "download_speed",
+    "upload_speed",
+    "num_connects",
+    "primary_port",
+    "local_ip",
+    "local_port",
+    "content_type",
+    "error",
+    "via_proxy",
+    "tunnel_establishment",
+    "token_fetch",
+    "variant",
+];
+
+/// Escape a CSV field according to RFC 4180
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Format a duration as milliseconds with microsecond precision
+fn csv_millis(duration: Option<Duration>) -> String {
+    duration
+        .map(|d| format!("{:.3}", d.as_micros() as f64 / 1000.0))
+        .unwrap_or_default()
+}
+
+/// Serialize the results as CSV with a header row and one row per request,
+/// the columns are listed in `CSV_COLUMNS`.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::to_csv;
+///
+/// let config = Config::default();
+/// let stats = vec![Stat::default(), Stat::default()];
+///
+/// let csv = to_csv(&config, &stats);
+///
+/// assert_eq!(csv.lines().count(), 3);
+/// assert!(csv.starts_with("run,url,method"));
+/// ```
+pub fn to_csv(config: &Config, stats: &[Stat]) -> String {
+    let mut csv = CSV_COLUMNS.join(",");
+    csv.push_str("\r\n");
+
+    for (index, stat) in stats.iter().enumerate() {
+        let method: &str = (&config.method).into();
+        let row = [
+            (index + 1).to_string(),
+            csv_field(&config.url),
+            method.to_string(),
+            csv_field(stat.ip_address.as_deref().unwrap_or_default()),
+            csv_field(stat.http_version.as_deref().unwrap_or_default()),
+            stat.response_status_code
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            csv_millis(Some(stat.name_lookup)),
+            csv_millis(Some(stat.connect)),
+            csv_millis(Some(stat.app_connect)),
+            csv_millis(Some(stat.pre_transfer)),
+            csv_millis(Some(stat.start_transfer)),
+            csv_millis(Some(stat.total)),
+            csv_millis(stat.dns_lookup()),
+            csv_millis(stat.tcp_handshake()),
+            csv_millis(stat.tls_handshake()),
+            csv_millis(stat.server_processing()),
+            csv_millis(stat.content_transfer()),
+            csv_field(stat.effective_url.as_deref().unwrap_or_default()),
+            stat.redirect_count.to_string(),
+            csv_millis(Some(stat.redirect_time)),
+            stat.download_size.to_string(),
+            stat.upload_size.to_string(),
+            stat.header_size.to_string(),
+            stat.request_size.to_string(),
+            format!("{:.0}", stat.download_speed),
+            format!("{:.0}", stat.upload_speed),
+            stat.num_connects.to_string(),
+            stat.primary_port
+                .map(|port| port.to_string())
+                .unwrap_or_default(),
+            csv_field(stat.local_ip.as_deref().unwrap_or_default()),
+            stat.local_port
+                .map(|port| port.to_string())
+                .unwrap_or_default(),
+            csv_field(stat.content_type.as_deref().unwrap_or_default()),
+            csv_field(stat.error.as_deref().unwrap_or_default()),
+            stat.via_proxy
+                .map(|used| used.to_string())
+                .unwrap_or_default(),
+            csv_millis(stat.tunnel_establishment()),
+            csv_millis(stat.token_fetch),
+            csv_field(stat.variant.as_deref().unwrap_or_default()),
+        ];
+
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+/// Write the report of the results in `Config::format` to `Config::report_file`,
+/// or to stdout when no report file is given. The screen format has no report.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::network::{Config, Stat};
+/// use cetar::output::{write_report, Format};
+///
+/// let config = Config {
+///     format: Format::Csv,
+///     ..Default::default()
+/// };
+/// let stats = vec![Stat::default()];
+///
+/// write_report(&config, &stats).unwrap();
+/// ```
+pub fn write_report(config: &Config, stats: &[Stat]) -> crate::Result<()> {
+    let report = match config.format {
+        Format::Screen => return Ok(()),
+        #[cfg(feature = "serde")]
+        Format::Json => to_json(config, stats)? + "\n",
+        Format::Csv => to_csv(config, stats),
+        #[cfg(feature = "serde")]
+        Format::Ndjson => to_ndjson(config, stats)?,
+    };
+
+    match &config.report_file {
+        Some(report_file) => std::fs::write(report_file.as_ref(), report)?,
+        None => print!("{report}"),
+    }
 
     Ok(())
 }
@@ -320,7 +1176,7 @@ mod test {
             ..Stat::default()
         };
         let config = Config::default();
-        let screen = Screen::new(&config, &stat);
+        let screen = Screen::new(&config, stat);
 
         let events = vec![
             (NetworkEvent::dns_lookup(stat), "█"),
@@ -348,7 +1204,7 @@ mod test {
             ..Stat::default()
         };
         let config = Config::default();
-        let screen = Screen::new(&config, &stat);
+        let screen = Screen::new(&config, stat);
 
         let events = vec![
             NetworkEvent::dns_lookup(stat),
@@ -373,7 +1229,7 @@ mod test {
             ..Stat::default()
         };
         let config = Config::default();
-        let screen = Screen::new(&config, &stat);
+        let screen = Screen::new(&config, stat);
 
         screen.display_network_timings();
     }
@@ -390,11 +1246,305 @@ mod test {
             ..Stat::default()
         };
         let config = Config::default();
-        let screen = Screen::new(&config, &stat);
+        let screen = Screen::new(&config, stat);
 
         screen.display_detailed_timings();
     }
 
+    #[test]
+    fn test_display_statistics() {
+        let stats = (1..=3)
+            .map(|i| Stat {
+                name_lookup: Duration::from_millis(i),
+                connect: Duration::from_millis(i * 2),
+                total: Duration::from_millis(i * 3),
+                ..Stat::default()
+            })
+            .collect::<Vec<_>>();
+        let statistics = Statistics::from(stats.as_slice());
+        let config = Config::default();
+        let screen = Screen::new(&config, &stats[2]).with_statistics(&statistics);
+
+        screen.display_statistics(&statistics);
+        screen.display();
+    }
+
+    #[test]
+    fn test_display_violations() {
+        let stat = Stat {
+            connect: Duration::from_millis(10),
+            app_connect: Duration::from_millis(70),
+            start_transfer: Duration::from_millis(250),
+            total: Duration::from_millis(300),
+            ..Stat::default()
+        };
+        let config = Config {
+            budgets: vec![
+                crate::budget::Budget::from_str("tls=50ms").unwrap(),
+                crate::budget::Budget::from_str("ttfb=200ms").unwrap(),
+            ],
+            ..Config::default()
+        };
+        let violations = crate::budget::check(&config.budgets, std::slice::from_ref(&stat));
+        let screen = Screen::new(&config, &stat).with_violations(&violations);
+
+        assert!(screen
+            .violation(Some(Metric::Phase(Phase::TlsHandshake)))
+            .is_some());
+        assert!(screen.violation(Some(Metric::TimeToFirstByte)).is_some());
+        assert!(screen
+            .violation(Some(Metric::Phase(Phase::Total)))
+            .is_none());
+        assert!(screen.violation(None).is_none());
+        assert_eq!(
+            screen.paint_name("TLS", Some(Metric::Phase(Phase::TlsHandshake))),
+            "\x1b[31mTLS\x1b[0m"
+        );
+
+        screen.display_violations();
+        screen.display();
+    }
+
+    #[test]
+    fn test_display_failures() {
+        let stat = Stat {
+            response_status_code: Some(500),
+            ..Stat::default()
+        };
+        let config = Config {
+            assertions: vec![
+                crate::assertion::Assertion::Status(200),
+                crate::assertion::Assertion::BodyContains("ok".to_string()),
+            ],
+            ..Config::default()
+        };
+        let failures = crate::assertion::check(&config.assertions, std::slice::from_ref(&stat));
+        let screen = Screen::new(&config, &stat).with_failures(&failures);
+
+        assert_eq!(failures.len(), 2);
+
+        screen.display_failures();
+        screen.display();
+    }
+
+    #[test]
+    fn test_display_redirects() {
+        let hop = Stat {
+            name_lookup: Duration::from_millis(1),
+            total: Duration::from_millis(3),
+            response_status_code: Some(301),
+            effective_url: Some("http://localhost/a".to_string()),
+            ..Stat::default()
+        };
+        let stat = Stat {
+            total: Duration::from_millis(2),
+            response_status_code: Some(200),
+            effective_url: Some("http://localhost/b".to_string()),
+            redirect_count: 1,
+            redirect_time: hop.total,
+            redirects: vec![hop],
+            ..Stat::default()
+        };
+        let config = Config::default();
+        let screen = Screen::new(&config, &stat);
+
+        assert_eq!(
+            Screen::phase_values(&stat.redirects[0]),
+            "    1.00ms         -         -         -    3.00ms    3.00ms"
+        );
+
+        screen.display_redirects();
+        screen.display();
+    }
+
+    #[test]
+    fn test_display_comparison() {
+        let stat = Stat {
+            name_lookup: Duration::from_millis(1),
+            total: Duration::from_millis(4),
+            http_version: Some("2".to_string()),
+            response_status_code: Some(200),
+            ..Stat::default()
+        };
+        let comparison = vec![
+            Variant {
+                label: "HTTP/3".to_string(),
+                result: Err(crate::Error::InvalidInput(
+                    "HTTP/3 is not supported".to_string(),
+                )),
+            },
+            Variant {
+                label: "HTTP/2".to_string(),
+                result: Ok(Stat {
+                    total: Duration::from_millis(4),
+                    ..Stat::default()
+                }),
+            },
+        ];
+        let violations = vec![Violation {
+            run: 0,
+            budget: crate::budget::Budget::from_str("total=1ms").unwrap(),
+            actual: Duration::from_millis(4),
+        }];
+        let config = Config::default();
+        let screen = Screen::new(&config, &stat)
+            .with_comparison(&comparison)
+            .with_violations(&violations);
+
+        assert_eq!(screen.run_label(0), " (HTTP/2)");
+        assert_eq!(screen.run_label(1), "");
+
+        screen.display_comparison();
+        screen.display();
+    }
+
+    #[test]
+    fn test_display_cookies() {
+        let redirect = Stat {
+            cookies: vec![Cookie::from_str("session=abc; Path=/; Secure; HttpOnly").unwrap()],
+            ..Stat::default()
+        };
+        let stat = Stat {
+            redirects: vec![redirect],
+            cookies: vec![Cookie::from_str("theme=dark; Max-Age=60; SameSite=Lax").unwrap()],
+            ..Stat::default()
+        };
+        let config = Config::default();
+        let screen = Screen::new(&config, &stat);
+
+        screen.display_cookies(&stat.cookies.iter().collect::<Vec<_>>());
+        screen.display();
+    }
+
+    #[test]
+    fn test_phase_deltas() {
+        let base = Stat {
+            name_lookup: Duration::from_millis(1),
+            connect: Duration::from_millis(3),
+            total: Duration::from_millis(10),
+            ..Stat::default()
+        };
+        let other = Stat {
+            name_lookup: Duration::from_millis(1),
+            connect: Duration::from_millis(2),
+            total: Duration::from_millis(15),
+            ..Stat::default()
+        };
+
+        let deltas = Screen::phase_deltas(&base, &other);
+        assert!(deltas.contains("+0.00ms"));
+        assert!(deltas.contains(&make_color!(Color::Green as u8, "   -1.00ms")));
+        assert!(deltas.contains(&make_color!(Color::Red as u8, "   +5.00ms")));
+
+        let comparison = vec![
+            Variant {
+                label: "IPv4".to_string(),
+                result: Ok(base.clone()),
+            },
+            Variant {
+                label: "IPv6".to_string(),
+                result: Ok(other),
+            },
+        ];
+        let config = Config::default();
+        Screen::new(&config, &base)
+            .with_comparison(&comparison)
+            .display_comparison();
+    }
+
+    #[test]
+    fn test_display_network_timings_proxy() {
+        let stat = Stat {
+            name_lookup: Duration::from_millis(1),
+            connect: Duration::from_millis(3),
+            app_connect: Duration::from_millis(10),
+            pre_transfer: Duration::from_millis(10),
+            start_transfer: Duration::from_millis(20),
+            total: Duration::from_millis(25),
+            via_proxy: Some(true),
+            tunnel: Some(Duration::from_millis(4)),
+            ..Stat::default()
+        };
+        let config = Config::default();
+        let screen = Screen::new(&config, &stat);
+
+        assert_eq!(
+            NetworkEvent::proxy_connect(&stat)
+                .unwrap()
+                .duration
+                .as_millis(),
+            2
+        );
+        assert_eq!(
+            NetworkEvent::tunnel_establishment(&stat)
+                .unwrap()
+                .duration
+                .as_millis(),
+            4
+        );
+        assert!(NetworkEvent::proxy_connect(&Stat::default()).is_none());
+
+        screen.display_network_timings();
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        let table = vec![
+            (0, "0 B"),
+            (1023, "1023 B"),
+            (1024, "1.00 KB"),
+            (1536, "1.50 KB"),
+            (5 * 1024 * 1024, "5.00 MB"),
+            (3 * 1024 * 1024 * 1024, "3.00 GB"),
+        ];
+
+        for (bytes, expected) in table {
+            assert_eq!(Screen::format_bytes(bytes), expected);
+        }
+    }
+
+    #[test]
+    fn test_display_transfer() {
+        let stat = Stat {
+            ip_address: Some("127.0.0.1".to_string()),
+            primary_port: Some(443),
+            download_size: 4096,
+            download_speed: 1024.0,
+            num_connects: 1,
+            ..Stat::default()
+        };
+        let config = Config::default();
+        let screen = Screen::new(&config, &stat);
+
+        screen.display_transfer();
+    }
+
+    #[test]
+    fn test_display_tls() {
+        let stat = Stat {
+            tls: Some(Tls {
+                version: Some("TLSv1.3".to_string()),
+                cipher: Some("TLS_AES_128_GCM_SHA256".to_string()),
+                certificates: vec![crate::tls::Certificate {
+                    subject: Some("CN = localhost".to_string()),
+                    subject_alt_names: vec!["DNS:localhost".to_string()],
+                    expires_in_days: Some(5),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Stat::default()
+        };
+        let config = Config {
+            warn_cert_expiry: Some(Duration::from_secs(30 * 86400)),
+            ..Config::default()
+        };
+        let screen = Screen::new(&config, &stat);
+
+        screen.display_tls(stat.tls.as_ref().unwrap());
+        screen.display();
+    }
+
     #[test]
     fn test_display_response_headers() {
         let stat = Stat {
@@ -432,6 +1582,165 @@ mod test {
         screen.display();
     }
 
+    #[test]
+    fn test_format_try_from() {
+        assert_eq!(Format::try_from("screen").unwrap(), Format::Screen);
+        #[cfg(feature = "serde")]
+        assert_eq!(Format::try_from("JSON").unwrap(), Format::Json);
+        assert!(Format::try_from("invalid").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let stat = Stat {
+            ip_address: Some("127.0.0.1".to_string()),
+            name_lookup: Duration::from_millis(1),
+            connect: Duration::from_millis(2),
+            app_connect: Duration::from_millis(1),
+            pre_transfer: Duration::from_millis(4),
+            start_transfer: Duration::from_millis(5),
+            total: Duration::from_millis(6),
+            response_status_code: Some(200),
+            response_headers: vec![Header::from_str("Content-Type: text/html").unwrap()],
+            variant: Some("IPv4".to_string()),
+            ..Stat::default()
+        };
+        let config = Config {
+            url: "http://localhost/".into(),
+            ..Config::default()
+        };
+
+        let json = to_json(&config, std::slice::from_ref(&stat)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1);
+        let value = &value[0];
+
+        assert_eq!(value["url"], "http://localhost/");
+        assert_eq!(value["method"], "GET");
+        assert_eq!(value["ip_address"], "127.0.0.1");
+        assert_eq!(value["response_status_code"], 200);
+        assert_eq!(value["variant"], "IPv4");
+        assert_eq!(value["total"], 6.0);
+        assert_eq!(value["phases"]["dns_lookup"], 1.0);
+        assert_eq!(value["phases"]["tcp_handshake"], 1.0);
+        assert!(value["phases"]["tls_handshake"].is_null());
+        assert_eq!(value["phases"]["total"], 6.0);
+        assert_eq!(value["response_headers"][0]["value"], "text/html");
+
+        let stats = vec![Stat::default(), Stat::default()];
+        let json = to_json(&config, &stats).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value.as_array().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_ndjson() {
+        let stats = vec![
+            Stat {
+                total: Duration::from_millis(1),
+                ..Stat::default()
+            },
+            Stat {
+                total: Duration::from_millis(2),
+                ..Stat::default()
+            },
+        ];
+        let config = Config::default();
+
+        let ndjson = to_ndjson(&config, &stats).unwrap();
+        let lines = ndjson.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        for (line, expected) in lines.iter().zip([1.0, 2.0]) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["total"], expected);
+        }
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let stat = Stat {
+            ip_address: Some("127.0.0.1".to_string()),
+            http_version: Some("1.1".to_string()),
+            name_lookup: Duration::from_micros(1500),
+            connect: Duration::from_millis(2),
+            app_connect: Duration::from_millis(1),
+            pre_transfer: Duration::from_millis(4),
+            start_transfer: Duration::from_millis(5),
+            total: Duration::from_millis(6),
+            response_status_code: Some(200),
+            download_size: 2048,
+            header_size: 120,
+            request_size: 80,
+            download_speed: 341333.4,
+            num_connects: 1,
+            primary_port: Some(80),
+            local_ip: Some("127.0.0.1".to_string()),
+            local_port: Some(50000),
+            content_type: Some("text/plain".to_string()),
+            via_proxy: Some(false),
+            variant: Some("HTTP/1.1".to_string()),
+            ..Stat::default()
+        };
+        let config = Config {
+            url: "http://localhost/?a=1,b=\"2\"".into(),
+            ..Config::default()
+        };
+
+        let csv = to_csv(&config, &[Stat::default(), stat]);
+        let lines = csv.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], CSV_COLUMNS.join(","));
+        assert_eq!(
+            lines[2],
+            "2,\"http://localhost/?a=1,b=\"\"2\"\"\",GET,127.0.0.1,1.1,200,\
+            1.500,2.000,1.000,4.000,5.000,6.000,1.500,0.500,,1.000,1.000,,0,0.000,\
+            2048,0,120,80,341333,0,1,80,127.0.0.1,50000,text/plain,,false,,,HTTP/1.1"
+        );
+        assert_eq!(lines[3], "");
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn test_write_report() {
+        let stat = Stat {
+            response_body: "Hello, World!".as_bytes().to_vec(),
+            ..Stat::default()
+        };
+        let config = Config {
+            output: Some("report_body.txt".into()),
+            report_file: Some("report.csv".into()),
+            format: Format::Csv,
+            ..Config::default()
+        };
+
+        handle_output(&config, &stat).unwrap();
+        write_report(&config, std::slice::from_ref(&stat)).unwrap();
+
+        let contents = std::fs::read_to_string("report.csv").unwrap();
+        assert!(contents.starts_with("run,url,method"));
+        assert_eq!(contents.lines().count(), 2);
+        assert_eq!(
+            std::fs::read_to_string("report_body.txt").unwrap(),
+            "Hello, World!"
+        );
+
+        // Clean up
+        std::fs::remove_file("report.csv").unwrap();
+        std::fs::remove_file("report_body.txt").unwrap();
+    }
+
     #[test]
     fn test_handle_output() {
         let stat = Stat {
@@ -452,4 +1761,42 @@ mod test {
         // Clean up
         std::fs::remove_file("output.txt").unwrap();
     }
+
+    #[test]
+    fn test_handle_output_binary() {
+        let body = vec![
+            0x89, b'P', b'N', b'G', b'\r', b'\n', b'\r', b'\n', 0xff, 0x00,
+        ];
+        let stat = Stat {
+            response_body: body.clone(),
+            ..Stat::default()
+        };
+        let config = Config {
+            output: Some("output.bin".into()),
+            ..Config::default()
+        };
+        handle_output(&config, &stat).unwrap();
+
+        assert_eq!(std::fs::read("output.bin").unwrap(), body);
+
+        // Clean up
+        std::fs::remove_file("output.bin").unwrap();
+    }
+
+    #[test]
+    fn test_handle_output_streamed() {
+        let stat = Stat::default();
+        let config = Config {
+            output: Some("streamed.bin".into()),
+            sink: Sink::File("streamed.bin".into()),
+            ..Config::default()
+        };
+        std::fs::write("streamed.bin", "streamed").unwrap();
+        handle_output(&config, &stat).unwrap();
+
+        assert_eq!(std::fs::read_to_string("streamed.bin").unwrap(), "streamed");
+
+        // Clean up
+        std::fs::remove_file("streamed.bin").unwrap();
+    }
 }
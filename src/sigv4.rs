@@ -0,0 +1,451 @@
+use crate::body::Body;
+use crate::encoding::{hex, percent_decode, percent_encode};
+use crate::network::{Config, Header, Method};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// SigV4 struct to store the signing scope, the same as curl's `--aws-sigv4`
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::sigv4::SigV4;
+/// use std::str::FromStr;
+///
+/// let sigv4 = SigV4::from_str("aws:eu-west-1:execute-api").unwrap();
+///
+/// assert_eq!(sigv4.provider, "aws");
+/// assert_eq!(sigv4.region, "eu-west-1");
+/// assert_eq!(sigv4.service, "execute-api");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigV4 {
+    /// Provider name, `aws` for Amazon Web Services
+    pub provider: String,
+    /// Region of the service, e.g. `us-east-1`
+    pub region: String,
+    /// Name of the service, e.g. `s3` or `execute-api`
+    pub service: String,
+}
+
+impl std::str::FromStr for SigV4 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split(':').collect::<Vec<_>>().as_slice() {
+            [provider, region, service]
+                if [provider, region, service]
+                    .iter()
+                    .all(|part| !part.is_empty()) =>
+            {
+                Ok(Self {
+                    provider: provider.to_lowercase(),
+                    region: region.to_string(),
+                    service: service.to_string(),
+                })
+            }
+            _ => anyhow::bail!("Invalid SigV4 format, please use provider:region:service"),
+        }
+    }
+}
+
+/// Credentials struct to store the access key that signs the requests
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::sigv4::Credentials;
+///
+/// let credentials = Credentials {
+///     access_key_id: "AKIDEXAMPLE".to_string(),
+///     secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+///     session_token: None,
+/// };
+///
+/// assert!(!format!("{credentials:?}").contains("EXAMPLEKEY"));
+/// ```
+///
+#[derive(Clone, PartialEq)]
+pub struct Credentials {
+    /// Access key ID
+    pub access_key_id: String,
+    /// Secret access key
+    pub secret_access_key: String,
+    /// Session token of temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    /// Read the credentials from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`
+    /// and `AWS_SESSION_TOKEN` environment variables
+    pub fn from_env() -> Option<Self> {
+        Some(Self {
+            access_key_id: std::env::var("AWS_ACCESS_KEY_ID").ok()?,
+            secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY").ok()?,
+            session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+impl core::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &crate::auth::REDACTED)
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| crate::auth::REDACTED),
+            )
+            .finish()
+    }
+}
+
+/// Payload hash of a body that is not signed
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Hash of the request body, files are hashed as they are read. Standard input can only
+/// be read once, so it is left unsigned, which only S3 accepts.
+fn payload_hash(body: Option<&Body>, service: &str) -> crate::Result<String> {
+    let mut hasher = Sha256::new();
+    match body {
+        None => {}
+        Some(Body::Bytes(bytes)) => hasher.update(bytes),
+        Some(Body::Stdin) if service == "s3" => return Ok(UNSIGNED_PAYLOAD.to_string()),
+        Some(Body::Stdin) => {
+            return Err(crate::Error::InvalidInput(format!(
+                "SigV4 signing for {service} cannot sign a body streamed from standard input"
+            )))
+        }
+        Some(body) => {
+            std::io::copy(&mut body.reader()?, &mut hasher)?;
+        }
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+impl SigV4 {
+    /// Sign the request of the configuration at the Unix timestamp, returning the headers to send along.
+    /// The signature covers the method, URL, request headers and request body, a multipart
+    /// form is built by curl as it is sent and cannot be signed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use cetar::network::Config;
+    /// use cetar::sigv4::{Credentials, SigV4};
+    /// use std::str::FromStr;
+    ///
+    /// let config = Config {
+    ///     url: "https://example.amazonaws.com/".into(),
+    ///     ..Default::default()
+    /// };
+    /// let credentials = Credentials {
+    ///     access_key_id: "AKIDEXAMPLE".to_string(),
+    ///     secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+    ///     session_token: None,
+    /// };
+    ///
+    /// let sigv4 = SigV4::from_str("aws:us-east-1:service").unwrap();
+    /// let headers = sigv4.sign(&credentials, &config, 1440938160).unwrap();
+    ///
+    /// assert_eq!(headers[0].key, "X-Amz-Date");
+    /// assert_eq!(headers[0].value, "20150830T123600Z");
+    /// assert!(headers.last().unwrap().value.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request"));
+    /// ```
+    pub fn sign(
+        &self,
+        credentials: &Credentials,
+        conf: &Config,
+        timestamp: i64,
+    ) -> crate::Result<Vec<Header>> {
+        if !conf.form.is_empty() {
+            return Err(crate::Error::InvalidInput(
+                "SigV4 signing cannot sign a multipart form".to_string(),
+            ));
+        }
+
+        self.sign_request(
+            credentials,
+            &conf.method,
+            &conf.url,
+            &conf.request_headers,
+            conf.request_body.as_ref(),
+            timestamp,
+        )
+    }
+
+    /// Sign a request given by its parts, used as well for each hop of a redirect.
+    pub(crate) fn sign_request(
+        &self,
+        credentials: &Credentials,
+        method: &Method,
+        url: &str,
+        request_headers: &[Header],
+        body: Option<&Body>,
+        timestamp: i64,
+    ) -> crate::Result<Vec<Header>> {
+        let provider = self.provider.to_uppercase();
+        let prefix = match self.provider.as_str() {
+            "aws" => "X-Amz".to_string(),
+            name => format!("X-{}{}", name[..1].to_uppercase(), &name[1..]),
+        };
+        let algorithm = format!("{provider}4-HMAC-SHA256");
+        let date_time = crate::date::format_basic(timestamp);
+        let date = &date_time[..8];
+        let scope = format!(
+            "{date}/{}/{}/{}4_request",
+            self.region, self.service, self.provider
+        );
+        let payload_hash = payload_hash(body, &self.service)?;
+
+        let mut signed = vec![Header {
+            key: format!("{prefix}-Date"),
+            value: date_time.clone(),
+        }];
+        if self.service == "s3" {
+            signed.push(Header {
+                key: format!("{prefix}-Content-Sha256"),
+                value: payload_hash.clone(),
+            });
+        }
+        if let Some(token) = &credentials.session_token {
+            signed.push(Header {
+                key: format!("{prefix}-Security-Token"),
+                value: token.clone(),
+            });
+        }
+
+        let (_, authority, path_and_query) = crate::dns::split_url(url);
+        let (path, query) = path_and_query
+            .split_once('?')
+            .unwrap_or((path_and_query, ""));
+
+        let path = match path {
+            "" => "/".to_string(),
+            path if self.service == "s3" => percent_encode(&percent_decode(path), false),
+            path => percent_encode(
+                percent_encode(&percent_decode(path), false).as_bytes(),
+                false,
+            ),
+        };
+
+        let mut parameters = query
+            .split('&')
+            .filter(|parameter| !parameter.is_empty())
+            .map(|parameter| {
+                let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+                (
+                    percent_encode(&percent_decode(key), true),
+                    percent_encode(&percent_decode(value), true),
+                )
+            })
+            .collect::<Vec<_>>();
+        parameters.sort();
+        let query = parameters
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut headers = std::collections::BTreeMap::<String, Vec<String>>::new();
+        headers.insert("host".to_string(), vec![authority.to_string()]);
+        for header in request_headers
+            .iter()
+            .chain(&signed)
+            .filter(|header| !header.key.eq_ignore_ascii_case("authorization"))
+        {
+            headers.entry(header.key.to_lowercase()).or_default().push(
+                header
+                    .value
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+        let canonical_headers = headers
+            .iter()
+            .map(|(key, values)| format!("{key}:{}\n", values.join(",")))
+            .collect::<String>();
+        let signed_headers = headers.keys().cloned().collect::<Vec<_>>().join(";");
+
+        let method: &str = method.into();
+        let canonical_request = format!(
+            "{method}\n{path}\n{query}\n{canonical_headers}\n{signed_headers}\n{payload_hash}"
+        );
+        let string_to_sign = format!(
+            "{algorithm}\n{date_time}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [
+            date,
+            &self.region,
+            &self.service,
+            &format!("{}4_request", self.provider),
+        ]
+        .iter()
+        .fold(
+            format!("{provider}4{}", credentials.secret_access_key).into_bytes(),
+            |key, data| hmac(&key, data),
+        );
+        let signature = hex(&hmac(&key, &string_to_sign));
+
+        signed.push(Header {
+            key: "Authorization".to_string(),
+            value: format!(
+                "{algorithm} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                credentials.access_key_id
+            ),
+        });
+
+        Ok(signed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::body::FormPart;
+    use crate::network::Method;
+    use std::str::FromStr;
+
+    fn credentials() -> Credentials {
+        Credentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        }
+    }
+
+    fn signature(headers: &[Header]) -> &str {
+        let authorization = &headers.last().unwrap().value;
+        authorization.rsplit("Signature=").next().unwrap()
+    }
+
+    #[test]
+    fn test_sigv4_from_str() {
+        assert!(SigV4::from_str("aws:us-east-1").is_err());
+        assert!(SigV4::from_str("aws::s3").is_err());
+        assert_eq!(SigV4::from_str("AWS:us-east-1:s3").unwrap().provider, "aws");
+    }
+
+    #[test]
+    fn test_sign() {
+        let sigv4 = SigV4::from_str("aws:us-east-1:service").unwrap();
+        let table = vec![
+            (
+                Method::Get,
+                "https://example.amazonaws.com/",
+                "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31",
+            ),
+            (
+                Method::Get,
+                "https://example.amazonaws.com/?Param2=value2&Param1=value1",
+                "b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500",
+            ),
+            (
+                Method::Post,
+                "https://example.amazonaws.com/",
+                "5da7c1a2acd57cee7505fc6676e4e544621c30862966e37dddb68e92efbe5d6b",
+            ),
+        ];
+
+        for (method, url, expected) in table {
+            let config = Config {
+                url: url.into(),
+                method,
+                ..Default::default()
+            };
+            let headers = sigv4.sign(&credentials(), &config, 1440938160).unwrap();
+
+            assert_eq!(signature(&headers), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_payload_hash() {
+        let hello = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let path = std::env::temp_dir().join("cetar-sigv4-payload.txt");
+        std::fs::write(&path, "hello").unwrap();
+
+        let hash = |body: Option<&Body>, service| payload_hash(body, service).unwrap();
+
+        assert_eq!(hash(Some(&Body::from("hello")), "s3"), hello);
+        assert_eq!(hash(Some(&Body::File(path.clone())), "execute-api"), hello);
+        assert_eq!(hash(Some(&Body::Stdin), "s3"), UNSIGNED_PAYLOAD);
+        assert_eq!(
+            hash(None, "execute-api"),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(matches!(
+            payload_hash(Some(&Body::Stdin), "execute-api"),
+            Err(crate::Error::InvalidInput(_))
+        ));
+        assert!(matches!(
+            payload_hash(Some(&Body::File("/nonexistent/payload".into())), "s3"),
+            Err(crate::Error::Io(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sign_form() {
+        let sigv4 = SigV4::from_str("aws:us-east-1:s3").unwrap();
+        let config = Config {
+            url: "https://bucket.s3.amazonaws.com/".into(),
+            method: Method::Post,
+            form: vec![FormPart::from_str("file=@upload.bin").unwrap()],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            sigv4.sign(&credentials(), &config, 1440938160),
+            Err(crate::Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_s3_with_session_token() {
+        let sigv4 = SigV4::from_str("aws:us-east-1:s3").unwrap();
+        let credentials = Credentials {
+            session_token: Some("token".to_string()),
+            ..credentials()
+        };
+        let config = Config {
+            url: "https://bucket.s3.amazonaws.com/a%20b.txt".into(),
+            request_body: Some("hello".into()),
+            method: Method::Put,
+            ..Default::default()
+        };
+        let headers = sigv4.sign(&credentials, &config, 1440938160).unwrap();
+        let keys = headers
+            .iter()
+            .map(|header| header.key.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            keys,
+            vec![
+                "X-Amz-Date",
+                "X-Amz-Content-Sha256",
+                "X-Amz-Security-Token",
+                "Authorization"
+            ]
+        );
+        assert_eq!(
+            headers[1].value,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(headers[3]
+            .value
+            .contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token,"));
+    }
+}
@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use crate::network::{Phase, Stat};
+
+/// Summary of a set of duration samples
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::statistics::Summary;
+/// use std::time::Duration;
+///
+/// let samples = vec![
+///     Duration::from_millis(10),
+///     Duration::from_millis(20),
+///     Duration::from_millis(30),
+/// ];
+///
+/// let summary = Summary::try_from(samples.as_slice()).unwrap();
+///
+/// assert_eq!(summary.min, Duration::from_millis(10));
+/// assert_eq!(summary.max, Duration::from_millis(30));
+/// assert_eq!(summary.mean, Duration::from_millis(20));
+/// assert_eq!(summary.p50, Duration::from_millis(20));
+/// ```
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Summary {
+    /// Number of samples
+    pub samples: usize,
+    /// Minimum duration
+    pub min: Duration,
+    /// Maximum duration
+    pub max: Duration,
+    /// Arithmetic mean
+    pub mean: Duration,
+    /// Population standard deviation
+    pub stddev: Duration,
+    /// 50th percentile (median)
+    pub p50: Duration,
+    /// 90th percentile
+    pub p90: Duration,
+    /// 95th percentile
+    pub p95: Duration,
+    /// 99th percentile
+    pub p99: Duration,
+}
+
+impl Summary {
+    /// Get the percentile from sorted samples using the nearest-rank method
+    fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+        let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+impl TryFrom<&[Duration]> for Summary {
+    type Error = anyhow::Error;
+
+    fn try_from(samples: &[Duration]) -> Result<Self, Self::Error> {
+        if samples.is_empty() {
+            anyhow::bail!("Cannot summarize an empty set of samples");
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Ok(Self {
+            samples: sorted.len(),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p50: Self::percentile(&sorted, 50.0),
+            p90: Self::percentile(&sorted, 90.0),
+            p95: Self::percentile(&sorted, 95.0),
+            p99: Self::percentile(&sorted, 99.0),
+        })
+    }
+}
+
+/// Statistics of every `Phase` over repeated requests
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::network::{Phase, Stat};
+/// use cetar::statistics::Statistics;
+/// use std::time::Duration;
+///
+/// let stats = vec![
+///     Stat {
+///         total: Duration::from_millis(100),
+///         ..Default::default()
+///     },
+///     Stat {
+///         total: Duration::from_millis(300),
+///         ..Default::default()
+///     },
+/// ];
+///
+/// let statistics = Statistics::from(stats.as_slice());
+/// let total = statistics.summary(Phase::Total).unwrap();
+///
+/// assert_eq!(statistics.count, 2);
+/// assert_eq!(total.mean, Duration::from_millis(200));
+/// ```
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Statistics {
+    /// Number of requests
+    pub count: usize,
+    /// Summary of each phase, phases without any samples are omitted
+    pub phases: Vec<(Phase, Summary)>,
+}
+
+impl Statistics {
+    /// Get the summary of the given phase
+    pub fn summary(&self, phase: Phase) -> Option<&Summary> {
+        self.phases
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|(_, summary)| summary)
+    }
+}
+
+impl From<&[Stat]> for Statistics {
+    fn from(stats: &[Stat]) -> Self {
+        let phases = Phase::ALL
+            .iter()
+            .filter_map(|phase| {
+                let samples = stats
+                    .iter()
+                    .filter_map(|stat| stat.phase(*phase))
+                    .collect::<Vec<_>>();
+
+                Summary::try_from(samples.as_slice())
+                    .ok()
+                    .map(|summary| (*phase, summary))
+            })
+            .collect();
+
+        Self {
+            count: stats.len(),
+            phases,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn test_summary() {
+        let samples = millis(&[2, 4, 4, 4, 5, 5, 7, 9]);
+        let summary = Summary::try_from(samples.as_slice()).unwrap();
+
+        assert_eq!(summary.samples, 8);
+        assert_eq!(summary.min, Duration::from_millis(2));
+        assert_eq!(summary.max, Duration::from_millis(9));
+        assert_eq!(summary.mean, Duration::from_millis(5));
+        assert_eq!(summary.stddev, Duration::from_millis(2));
+        assert_eq!(summary.p50, Duration::from_millis(4));
+        assert_eq!(summary.p90, Duration::from_millis(9));
+    }
+
+    #[test]
+    fn test_summary_percentiles() {
+        let samples = millis(&(1..=100).rev().collect::<Vec<_>>());
+        let summary = Summary::try_from(samples.as_slice()).unwrap();
+
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p90, Duration::from_millis(90));
+        assert_eq!(summary.p95, Duration::from_millis(95));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+    }
+
+    #[test]
+    fn test_summary_single_sample() {
+        let samples = millis(&[42]);
+        let summary = Summary::try_from(samples.as_slice()).unwrap();
+
+        assert_eq!(summary.min, Duration::from_millis(42));
+        assert_eq!(summary.p99, Duration::from_millis(42));
+        assert_eq!(summary.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_summary_empty() {
+        assert!(Summary::try_from([].as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_statistics() {
+        let stats = (1..=4)
+            .map(|i| Stat {
+                name_lookup: Duration::from_millis(i),
+                connect: Duration::from_millis(i * 2),
+                pre_transfer: Duration::from_millis(i * 2),
+                start_transfer: Duration::from_millis(i * 3),
+                total: Duration::from_millis(i * 4),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let statistics = Statistics::from(stats.as_slice());
+
+        assert_eq!(statistics.count, 4);
+        assert!(statistics.summary(Phase::TlsHandshake).is_none());
+
+        let dns = statistics.summary(Phase::DnsLookup).unwrap();
+        assert_eq!(dns.min, Duration::from_millis(1));
+        assert_eq!(dns.max, Duration::from_millis(4));
+
+        let total = statistics.summary(Phase::Total).unwrap();
+        assert_eq!(total.mean, Duration::from_millis(10));
+    }
+}
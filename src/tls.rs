@@ -0,0 +1,235 @@
+use crate::date;
+
+/// Certificate struct to store the details of a peer certificate
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::tls::Certificate;
+///
+/// let certificate = Certificate::from_certinfo(&[
+///     "Subject:CN = example.com".to_string(),
+///     "Issuer:CN = Example CA".to_string(),
+///     "Start date:Jan  1 00:00:00 2024 GMT".to_string(),
+///     "Expire date:Jan  1 00:00:00 2124 GMT".to_string(),
+///     "X509v3 Subject Alternative Name:DNS:example.com, DNS:www.example.com".to_string(),
+/// ]);
+///
+/// assert_eq!(certificate.subject.as_deref(), Some("CN = example.com"));
+/// assert_eq!(certificate.subject_alt_names, vec!["DNS:example.com", "DNS:www.example.com"]);
+/// assert!(certificate.expires_in_days.unwrap() > 0);
+/// ```
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Certificate {
+    /// Subject distinguished name
+    pub subject: Option<String>,
+    /// Issuer distinguished name
+    pub issuer: Option<String>,
+    /// Subject alternative names
+    pub subject_alt_names: Vec<String>,
+    /// Start of the validity period
+    pub not_before: Option<String>,
+    /// End of the validity period
+    pub not_after: Option<String>,
+    /// Days until the certificate expires, negative when already expired
+    pub expires_in_days: Option<i64>,
+}
+
+impl Certificate {
+    /// Create the certificate from the `Key:Value` entries curl reports with `CURLOPT_CERTINFO`
+    pub fn from_certinfo(entries: &[String]) -> Self {
+        let mut certificate = Self::default();
+
+        for (key, value) in entries.iter().filter_map(|entry| entry.split_once(':')) {
+            let value = value.trim().to_string();
+            match key.trim() {
+                "Subject" => certificate.subject = Some(value),
+                "Issuer" => certificate.issuer = Some(value),
+                "Start date" => certificate.not_before = Some(value),
+                "Expire date" => certificate.not_after = Some(value),
+                "X509v3 Subject Alternative Name" => {
+                    certificate.subject_alt_names = value
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+
+        certificate.expires_in_days = certificate
+            .not_after
+            .as_deref()
+            .and_then(date::parse_certificate_date)
+            .map(|expiry| (expiry - date::now()).div_euclid(86400));
+
+        certificate
+    }
+}
+
+/// Tls struct to store the negotiated TLS session and the peer certificate chain
+///
+/// The certificates are read with `CURLOPT_CERTINFO`, the session details are scraped from
+/// curl's verbose text, which is only enabled with `Config::tls_session` or `Config::verbose`.
+///
+/// # Example
+///
+/// ```rust
+/// use cetar::tls::Tls;
+///
+/// let mut tls = Tls::default();
+/// tls.parse_debug_text("SSL connection using TLSv1.3 / TLS_AES_256_GCM_SHA384 / X25519 / RSASSA-PSS");
+/// tls.parse_debug_text("ALPN: server accepted h2");
+///
+/// assert_eq!(tls.version.as_deref(), Some("TLSv1.3"));
+/// assert_eq!(tls.cipher.as_deref(), Some("TLS_AES_256_GCM_SHA384"));
+/// assert_eq!(tls.alpn.as_deref(), Some("h2"));
+/// ```
+///
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tls {
+    /// Negotiated TLS version, best-effort as the verbose text depends on the TLS backend
+    pub version: Option<String>,
+    /// Negotiated cipher suite, best-effort as the verbose text depends on the TLS backend
+    pub cipher: Option<String>,
+    /// Protocol negotiated with ALPN, best-effort as the verbose text depends on the TLS backend
+    pub alpn: Option<String>,
+    /// Peer certificate chain, starting with the server certificate
+    pub certificates: Vec<Certificate>,
+}
+
+impl Tls {
+    /// Collect the session details from a line of curl's verbose text output
+    pub fn parse_debug_text(&mut self, line: &str) {
+        let line = line.trim();
+
+        if let Some(session) = line.strip_prefix("SSL connection using ") {
+            let mut parts = session.split(" / ").map(|part| part.trim().to_string());
+            self.version = parts.next();
+            self.cipher = parts.next();
+        } else if let Some(protocol) = line
+            .strip_prefix("ALPN: server accepted ")
+            .or_else(|| line.strip_prefix("ALPN, server accepted to use "))
+        {
+            self.alpn = Some(protocol.trim().to_string());
+        }
+    }
+
+    /// Check whether a TLS session was negotiated
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none() && self.certificates.is_empty()
+    }
+
+    /// Days until the first certificate in the chain expires
+    pub fn expires_in_days(&self) -> Option<i64> {
+        self.certificates
+            .iter()
+            .filter_map(|certificate| certificate.expires_in_days)
+            .min()
+    }
+
+    /// Check whether a certificate in the chain expires within the duration
+    pub fn expires_within(&self, threshold: std::time::Duration) -> bool {
+        self.expires_in_days()
+            .is_some_and(|days| days * 86400 < threshold.as_secs() as i64)
+    }
+}
+
+/// Read the peer certificate chain from the handle, `Easy2::certinfo` must be enabled
+pub fn certificates<H>(handle: &curl::easy::Easy2<H>) -> Vec<Certificate> {
+    let mut info: *mut curl_sys::curl_certinfo = std::ptr::null_mut();
+
+    // SAFETY: CURLINFO_CERTINFO writes a pointer to a `curl_certinfo` owned by the handle,
+    // which stays valid until the next transfer on the handle.
+    unsafe {
+        let code =
+            curl_sys::curl_easy_getinfo(handle.raw(), curl_sys::CURLINFO_CERTINFO, &mut info);
+        if code != curl_sys::CURLE_OK || info.is_null() {
+            return vec![];
+        }
+
+        let info = &*info;
+        (0..info.num_of_certs.max(0) as usize)
+            .map(|index| {
+                let mut entries = vec![];
+                let mut list = *info.certinfo.add(index);
+                while !list.is_null() {
+                    let data = std::ffi::CStr::from_ptr((*list).data);
+                    entries.push(data.to_string_lossy().into_owned());
+                    list = (*list).next;
+                }
+                Certificate::from_certinfo(&entries)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_debug_text() {
+        let mut tls = Tls::default();
+        assert!(tls.is_empty());
+
+        tls.parse_debug_text("Connected to example.com (93.184.216.34) port 443");
+        assert!(tls.is_empty());
+
+        tls.parse_debug_text("SSL connection using TLSv1.2 / ECDHE-RSA-AES128-GCM-SHA256\n");
+        tls.parse_debug_text("ALPN, server accepted to use http/1.1\n");
+
+        assert_eq!(tls.version.as_deref(), Some("TLSv1.2"));
+        assert_eq!(tls.cipher.as_deref(), Some("ECDHE-RSA-AES128-GCM-SHA256"));
+        assert_eq!(tls.alpn.as_deref(), Some("http/1.1"));
+        assert!(!tls.is_empty());
+    }
+
+    #[test]
+    fn test_certificate_from_certinfo() {
+        let certificate = Certificate::from_certinfo(&[
+            "Subject:CN = expired.example.com".to_string(),
+            "Expire date:Jan  1 00:00:00 2020 GMT".to_string(),
+            "Cert:-----BEGIN CERTIFICATE-----".to_string(),
+            "garbage".to_string(),
+        ]);
+
+        assert_eq!(
+            certificate.subject.as_deref(),
+            Some("CN = expired.example.com")
+        );
+        assert!(certificate.issuer.is_none());
+        assert!(certificate.subject_alt_names.is_empty());
+        assert!(certificate.expires_in_days.unwrap() < 0);
+    }
+
+    #[test]
+    fn test_expires_in_days() {
+        let tls = Tls {
+            certificates: vec![
+                Certificate {
+                    expires_in_days: Some(90),
+                    ..Default::default()
+                },
+                Certificate::default(),
+                Certificate {
+                    expires_in_days: Some(12),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(tls.expires_in_days(), Some(12));
+        assert_eq!(Tls::default().expires_in_days(), None);
+
+        let day = std::time::Duration::from_secs(86400);
+        assert!(tls.expires_within(day * 30));
+        assert!(!tls.expires_within(day * 12));
+        assert!(!Tls::default().expires_within(day * 30));
+    }
+}